@@ -1,7 +1,11 @@
 use crate::component::{
+    create_subscription::CreateSubscription,
+    create_topic::CreateTopic,
     debug::DebugLogs,
+    delete_topic::DeleteTopic,
+    publish::Composer,
     pubsub::{self, Pubsub},
-    topics::Topics,
+    seek::Seek,
 };
 use crate::route::Route;
 use std::time::Instant;
@@ -12,8 +16,12 @@ pub struct App {
     pub pubsub: Pubsub,
     pub should_quit: bool,
     pub ticks: u64,
-    pub topics: Topics,
     pub debug_logs: DebugLogs,
+    pub composer: Composer,
+    pub create_topic: CreateTopic,
+    pub delete_topic: DeleteTopic,
+    pub create_subscription: CreateSubscription,
+    pub seek: Seek,
 }
 
 impl App {
@@ -24,8 +32,12 @@ impl App {
             pubsub: Pubsub::default(),
             should_quit: false,
             ticks: 0,
-            topics: Topics::new(),
             debug_logs: DebugLogs::default(),
+            composer: Composer::default(),
+            create_topic: CreateTopic::default(),
+            delete_topic: DeleteTopic::default(),
+            create_subscription: CreateSubscription::default(),
+            seek: Seek::default(),
         }
     }
 }
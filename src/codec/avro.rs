@@ -0,0 +1,224 @@
+use apache_avro::{
+    from_avro_datum,
+    schema::{NamesRef, ResolvedSchema},
+    Schema,
+};
+use serde_json::Value;
+
+pub fn compile(definition: &str) -> Result<Schema, String> {
+    Schema::parse_str(definition).map_err(|e| e.to_string())
+}
+
+/// Decodes a binary payload and renders it as pretty JSON. Unlike the Avro
+/// JSON encoding, union values are shown without their type wrapper.
+pub fn decode(schema: &Schema, payload: &[u8]) -> Result<String, String> {
+    let mut reader = payload;
+    let value = from_avro_datum(schema, &mut reader, None).map_err(|e| e.to_string())?;
+    let json = Value::try_from(value).map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&json).map_err(|e| e.to_string())
+}
+
+pub fn validate_binary(schema: &Schema, payload: &[u8]) -> Result<(), Vec<String>> {
+    let mut reader = payload;
+    match from_avro_datum(schema, &mut reader, None) {
+        Ok(_) if reader.is_empty() => Ok(()),
+        Ok(_) => Err(vec![format!(
+            "{} unexpected bytes after the end of the record",
+            reader.len()
+        )]),
+        Err(e) => Err(vec![e.to_string()]),
+    }
+}
+
+/// Checks `payload` against the Avro JSON encoding of `schema`, where
+/// non-null union values are wrapped in an object keyed by the branch type.
+/// Every mismatch is reported with the path to the offending value.
+pub fn validate_json(schema: &Schema, payload: &[u8]) -> Result<(), Vec<String>> {
+    let value: Value =
+        serde_json::from_slice(payload).map_err(|e| vec![format!("Invalid JSON: {e}")])?;
+    let resolved = ResolvedSchema::try_from(schema).map_err(|e| vec![e.to_string()])?;
+    let mut errors = Vec::new();
+    check(schema, &value, "$", resolved.get_names(), &mut errors);
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+fn check(schema: &Schema, value: &Value, path: &str, names: &NamesRef, errors: &mut Vec<String>) {
+    let matches = match (schema, value) {
+        (Schema::Ref { name }, _) => {
+            match names.get(name) {
+                Some(schema) => check(schema, value, path, names, errors),
+                None => errors.push(format!("{path}: unknown type {}", name.fullname(None))),
+            }
+            return;
+        }
+        (Schema::Union(union), _) => {
+            check_union(union.variants(), value, path, names, errors);
+            return;
+        }
+        (Schema::Record(record), Value::Object(fields)) => {
+            for field in &record.fields {
+                let field_path = format!("{path}.{}", field.name);
+                match fields.get(&field.name) {
+                    Some(value) => check(&field.schema, value, &field_path, names, errors),
+                    None if field.default.is_some() => {}
+                    None => errors.push(format!("{field_path}: missing required field")),
+                }
+            }
+            for key in fields.keys().filter(|k| !record.lookup.contains_key(*k)) {
+                errors.push(format!("{path}.{key}: not a field of {}", record.name.name));
+            }
+            return;
+        }
+        (Schema::Array(array), Value::Array(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                check(&array.items, item, &format!("{path}[{i}]"), names, errors);
+            }
+            return;
+        }
+        (Schema::Map(map), Value::Object(entries)) => {
+            for (key, value) in entries {
+                check(&map.types, value, &format!("{path}.{key}"), names, errors);
+            }
+            return;
+        }
+        (Schema::Enum(e), Value::String(symbol)) => {
+            if !e.symbols.contains(symbol) {
+                errors.push(format!(
+                    "{path}: \"{symbol}\" is not one of {}",
+                    e.symbols.join(", ")
+                ));
+            }
+            return;
+        }
+        (Schema::Fixed(fixed), Value::String(s)) => {
+            if s.chars().count() != fixed.size {
+                errors.push(format!("{path}: expected exactly {} bytes", fixed.size));
+            }
+            return;
+        }
+        (Schema::Decimal(decimal), _) => {
+            check(&decimal.inner, value, path, names, errors);
+            return;
+        }
+        (Schema::Null, Value::Null) => true,
+        (Schema::Boolean, Value::Bool(_)) => true,
+        (Schema::Int | Schema::Date | Schema::TimeMillis, Value::Number(n)) => {
+            n.as_i64().is_some_and(|n| i32::try_from(n).is_ok())
+        }
+        (
+            Schema::Long
+            | Schema::TimeMicros
+            | Schema::TimestampMillis
+            | Schema::TimestampMicros
+            | Schema::TimestampNanos
+            | Schema::LocalTimestampMillis
+            | Schema::LocalTimestampMicros
+            | Schema::LocalTimestampNanos,
+            Value::Number(n),
+        ) => n.as_i64().is_some(),
+        (Schema::Float | Schema::Double, Value::Number(_)) => true,
+        (
+            Schema::Bytes | Schema::String | Schema::Uuid | Schema::BigDecimal | Schema::Duration,
+            Value::String(_),
+        ) => true,
+        _ => false,
+    };
+    if !matches {
+        errors.push(format!(
+            "{path}: expected {}, found {}",
+            type_name(schema),
+            json_type(value)
+        ));
+    }
+}
+
+/// In Avro JSON, null is written as-is and any other union value is
+/// written as `{"<type>": value}`.
+fn check_union(
+    variants: &[Schema],
+    value: &Value,
+    path: &str,
+    names: &NamesRef,
+    errors: &mut Vec<String>,
+) {
+    let expected = || {
+        variants
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(" | ")
+    };
+    if value.is_null() {
+        if !variants.iter().any(|v| matches!(v, Schema::Null)) {
+            errors.push(format!(
+                "{path}: expected one of {}, found null",
+                expected()
+            ));
+        }
+        return;
+    }
+    let tagged = match value {
+        Value::Object(object) if object.len() == 1 => object.iter().next(),
+        _ => None,
+    };
+    let Some((tag, inner)) = tagged else {
+        errors.push(format!(
+            "{path}: union values must be wrapped as {{\"<type>\": value}}, one of {}",
+            expected()
+        ));
+        return;
+    };
+    match variants.iter().find(|v| type_name(v) == *tag) {
+        Some(variant) => check(variant, inner, &format!("{path}.{tag}"), names, errors),
+        None => errors.push(format!("{path}: \"{tag}\" is not one of {}", expected())),
+    }
+}
+
+/// The name a type is referred to by, which is also its union branch tag.
+fn type_name(schema: &Schema) -> String {
+    match schema {
+        Schema::Null => "null".into(),
+        Schema::Boolean => "boolean".into(),
+        Schema::Int | Schema::Date | Schema::TimeMillis => "int".into(),
+        Schema::Long
+        | Schema::TimeMicros
+        | Schema::TimestampMillis
+        | Schema::TimestampMicros
+        | Schema::TimestampNanos
+        | Schema::LocalTimestampMillis
+        | Schema::LocalTimestampMicros
+        | Schema::LocalTimestampNanos => "long".into(),
+        Schema::Float => "float".into(),
+        Schema::Double => "double".into(),
+        Schema::Bytes | Schema::BigDecimal => "bytes".into(),
+        Schema::String | Schema::Uuid => "string".into(),
+        Schema::Array(_) => "array".into(),
+        Schema::Map(_) => "map".into(),
+        Schema::Union(union) => union
+            .variants()
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        Schema::Record(record) => record.name.fullname(None),
+        Schema::Enum(e) => e.name.fullname(None),
+        Schema::Fixed(fixed) => fixed.name.fullname(None),
+        Schema::Duration => "fixed".into(),
+        Schema::Decimal(decimal) => type_name(&decimal.inner),
+        Schema::Ref { name } => name.fullname(None),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
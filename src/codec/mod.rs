@@ -0,0 +1,48 @@
+use crate::component::schemas::SchemaInfo;
+use google_cloud_googleapis::pubsub::v1::{schema::Type, Encoding};
+use prost_reflect::MessageDescriptor;
+
+pub mod avro;
+pub mod protobuf;
+
+/// A payload rendered as text by one of the decoders.
+#[derive(Debug, Clone)]
+pub struct Decoded {
+    /// What the payload was decoded as, such as a message type.
+    pub kind: String,
+    /// The pretty-printed payload, or why it could not be decoded.
+    pub result: Result<String, String>,
+}
+
+/// A compiled schema, used to run the checks Pub/Sub makes on messages
+/// published to a topic with a schema. Doing this locally shows every error
+/// before sending, and also works against the emulator.
+pub enum SchemaCodec {
+    Avro(apache_avro::Schema),
+    Protobuf(MessageDescriptor),
+}
+
+impl SchemaCodec {
+    pub fn compile(schema: &SchemaInfo) -> Result<Self, String> {
+        match schema.kind {
+            Type::Avro => avro::compile(&schema.definition).map(SchemaCodec::Avro),
+            Type::ProtocolBuffer => {
+                protobuf::compile(&schema.definition).map(SchemaCodec::Protobuf)
+            }
+            Type::Unspecified => Err("The schema has no type".to_string()),
+        }
+    }
+
+    /// Returns every problem found with `payload`, or an empty `Ok` if the
+    /// topic would accept it.
+    pub fn validate(&self, encoding: Encoding, payload: &[u8]) -> Result<(), Vec<String>> {
+        match (self, encoding) {
+            (SchemaCodec::Avro(schema), Encoding::Binary) => avro::validate_binary(schema, payload),
+            (SchemaCodec::Avro(schema), _) => avro::validate_json(schema, payload),
+            (SchemaCodec::Protobuf(message), Encoding::Binary) => {
+                protobuf::validate_binary(message, payload)
+            }
+            (SchemaCodec::Protobuf(message), _) => protobuf::validate_json(message, payload),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use prost_types::FileDescriptorSet;
+use protox::{
+    file::{ChainFileResolver, DescriptorSetFileResolver, File, FileResolver, GoogleFileResolver},
+    Compiler,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The name the inline definition is compiled under.
+const FILE_NAME: &str = "schema.proto";
+
+/// Serves the schema definition as a single in-memory file.
+struct InlineFile(String);
+
+impl FileResolver for InlineFile {
+    fn open_file(&self, name: &str) -> Result<File, protox::Error> {
+        match name == FILE_NAME {
+            true => File::from_source(name, &self.0),
+            false => Err(protox::Error::file_not_found(name)),
+        }
+    }
+}
+
+/// Compiles a Pub/Sub protocol buffer schema, which defines exactly one
+/// top-level message.
+pub fn compile(definition: &str) -> Result<MessageDescriptor, String> {
+    let mut resolver = ChainFileResolver::new();
+    resolver.add(InlineFile(definition.to_string()));
+    resolver.add(GoogleFileResolver::new());
+    let mut compiler = Compiler::with_file_resolver(resolver);
+    compiler.open_file(FILE_NAME).map_err(|e| e.to_string())?;
+    compiler
+        .descriptor_pool()
+        .get_file_by_name(FILE_NAME)
+        .and_then(|file| file.messages().next())
+        .ok_or("The schema does not define a message".to_string())
+}
+
+pub fn validate_binary(message: &MessageDescriptor, payload: &[u8]) -> Result<(), Vec<String>> {
+    DynamicMessage::decode(message.clone(), payload)
+        .map(|_| ())
+        .map_err(|e| vec![e.to_string()])
+}
+
+/// Checks `payload` against the proto3 JSON mapping of `message`.
+pub fn validate_json(message: &MessageDescriptor, payload: &[u8]) -> Result<(), Vec<String>> {
+    let mut deserializer = serde_json::Deserializer::from_slice(payload);
+    DynamicMessage::deserialize(message.clone(), &mut deserializer)
+        .and_then(|_| deserializer.end())
+        .map_err(|e| vec![e.to_string()])
+}
+
+/// Loads message types from either a serialized `FileDescriptorSet` or a
+/// directory of `.proto` files, which is also used as the import root.
+pub fn load_descriptors(source: &Path) -> Result<DescriptorPool, String> {
+    let compiler = match source.is_dir() {
+        true => {
+            let mut files = Vec::new();
+            find_proto_files(source, &mut files).map_err(|e| e.to_string())?;
+            if files.is_empty() {
+                return Err(format!("No .proto files in {}", source.display()));
+            }
+            let mut compiler = Compiler::new([source]).map_err(|e| e.to_string())?;
+            compiler.open_files(files).map_err(|e| e.to_string())?;
+            compiler
+        }
+        false => {
+            let bytes = fs::read(source).map_err(|e| format!("{}: {}", source.display(), e))?;
+            let set = FileDescriptorSet::decode(bytes.as_slice())
+                .map_err(|e| format!("{} is not a descriptor set: {}", source.display(), e))?;
+            let names: Vec<String> = set.file.iter().map(|f| f.name().to_string()).collect();
+            // Sets built without --include_imports still resolve the
+            // well-known types.
+            let mut resolver = ChainFileResolver::new();
+            resolver.add(DescriptorSetFileResolver::new(set));
+            resolver.add(GoogleFileResolver::new());
+            let mut compiler = Compiler::with_file_resolver(resolver);
+            compiler.open_files(names).map_err(|e| e.to_string())?;
+            compiler
+        }
+    };
+    Ok(compiler.descriptor_pool())
+}
+
+fn find_proto_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_proto_files(&path, files)?;
+        } else if path.extension().is_some_and(|ext| ext == "proto") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Decodes a binary payload and renders it using the proto3 JSON mapping.
+pub fn decode(message: &MessageDescriptor, payload: &[u8]) -> Result<String, String> {
+    let decoded = DynamicMessage::decode(message.clone(), payload).map_err(|e| e.to_string())?;
+    serde_json::to_string_pretty(&decoded).map_err(|e| e.to_string())
+}
@@ -0,0 +1,575 @@
+use crate::{
+    component::{
+        publish::Composer,
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::{send_event, AppEvent},
+    input::{handled, handled_empty, InputHandled},
+    records::{self, Record},
+    util::{parse_rate, short_name},
+};
+use google_cloud_googleapis::pubsub::v1::PubsubMessage;
+use google_cloud_pubsub::{
+    publisher::{Publisher, PublisherConfig},
+    topic::Topic,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
+    Frame,
+};
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc, watch, Semaphore},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// What the form describes: publish the records of `file` to `topic`.
+#[derive(Debug, Clone)]
+pub struct BulkPublishJob {
+    pub topic: String,
+    pub file: PathBuf,
+    /// Time between messages, `None` for as fast as possible.
+    pub period: Option<Duration>,
+    /// How many messages may be waiting for the server at once.
+    pub concurrency: usize,
+    pub results: PathBuf,
+    /// Ordering keys the composer paused on this topic after a failed
+    /// publish. Their lines are skipped until the key is resumed.
+    pub paused_keys: HashSet<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BulkProgress {
+    pub published: usize,
+    pub failed: usize,
+    /// Failures since the last report, by line.
+    pub failures: Vec<(usize, String)>,
+    /// Why nothing could be published, e.g. the file couldn't be read.
+    pub error: Option<String>,
+    pub done: bool,
+}
+
+/// The file publish dialog, and the job started from it which keeps running
+/// in a background task when the dialog is closed.
+pub struct BulkPublish {
+    pub topic: Option<String>,
+    pub form: Form,
+    pub error: Option<String>,
+    pub file: Option<PathBuf>,
+    pub total: usize,
+    pub progress: BulkProgress,
+    pub results: Option<PathBuf>,
+    /// Whether the job was asked to stop. It keeps running until the
+    /// messages in flight are done and the results are written.
+    pub cancelled: bool,
+    started: Instant,
+    finished: Option<Duration>,
+    task: Option<(JoinHandle<()>, watch::Sender<bool>)>,
+}
+
+impl Default for BulkPublish {
+    fn default() -> Self {
+        Self {
+            topic: None,
+            form: Form::new(vec![
+                Field::Text(TextField::new(
+                    "file",
+                    "File (.ndjson or .csv) or directory of payloads",
+                )),
+                Field::Text(TextField::new(
+                    "rate",
+                    "Messages per second (0 for unlimited)",
+                )),
+                Field::Text(TextField::new("concurrency", "Messages in flight at once")),
+                Field::Text(TextField::new(
+                    "results",
+                    "Results file (empty for <file>.results.ndjson)",
+                )),
+            ]),
+            error: None,
+            file: None,
+            total: 0,
+            progress: BulkProgress::default(),
+            results: None,
+            cancelled: false,
+            started: Instant::now(),
+            finished: None,
+            task: None,
+        }
+    }
+}
+
+const DEFAULT_RATE: &str = "100";
+const DEFAULT_CONCURRENCY: &str = "16";
+/// Failures kept for display, the results file has all of them.
+const MAX_FAILURES: usize = 500;
+const REPORT_INTERVAL: Duration = Duration::from_millis(250);
+
+impl BulkPublish {
+    pub fn is_open(&self) -> bool {
+        self.topic.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    fn job(&self, composer: &Composer) -> Result<BulkPublishJob, String> {
+        let topic = self.topic.clone().ok_or("No topic selected")?;
+        let file = self.form.get("file").trim();
+        if file.is_empty() {
+            return Err("Enter the file to publish".to_string());
+        }
+        let file = PathBuf::from(file);
+        let period = parse_rate(self.form.get("rate"))?;
+        let concurrency: usize = self
+            .form
+            .get("concurrency")
+            .trim()
+            .parse()
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or("The concurrency must be a positive number")?;
+        let results = match self.form.get("results").trim() {
+            "" => PathBuf::from(format!("{}.results.ndjson", file.display())),
+            results => PathBuf::from(results),
+        };
+        Ok(BulkPublishJob {
+            file,
+            period,
+            concurrency,
+            results,
+            paused_keys: composer
+                .paused_keys
+                .iter()
+                .filter(|(t, _)| *t == topic)
+                .map(|(_, key)| key.clone())
+                .collect(),
+            topic,
+        })
+    }
+
+    pub fn start(&mut self, topic: Topic, job: BulkPublishJob) {
+        if let Some((handle, _)) = self.task.take() {
+            handle.abort();
+        }
+        self.file = Some(job.file.clone());
+        self.results = Some(job.results.clone());
+        self.total = 0;
+        self.progress = BulkProgress::default();
+        self.cancelled = false;
+        self.started = Instant::now();
+        self.finished = None;
+        let (stop_tx, stop_rx) = watch::channel(false);
+        self.task = Some((spawn_publish(topic, job, stop_rx), stop_tx));
+    }
+
+    /// Stops publishing new messages. The job reports itself done once the
+    /// messages already sent are answered and the results are written.
+    pub fn stop(&mut self) {
+        if let Some((_, stop)) = &self.task {
+            let _ = stop.send(true);
+            self.cancelled = true;
+        }
+    }
+
+    fn add_progress(&mut self, progress: BulkProgress) {
+        let mut failures = std::mem::take(&mut self.progress.failures);
+        failures.extend(progress.failures);
+        failures.truncate(MAX_FAILURES);
+        self.progress = BulkProgress {
+            failures,
+            ..progress
+        };
+    }
+}
+
+/// Reads the records of the job's file, without holding up the event loop.
+async fn load(job: &BulkPublishJob) -> Result<(Vec<records::Line>, BufWriter<File>), String> {
+    let file = job.file.clone();
+    let lines = tokio::task::spawn_blocking(move || records::read(&file))
+        .await
+        .map_err(|e| e.to_string())??;
+    if lines.is_empty() {
+        return Err(format!("{} has no messages", job.file.display()));
+    }
+    let results =
+        File::create(&job.results).map_err(|e| format!("{}: {}", job.results.display(), e))?;
+    Ok((lines, BufWriter::new(results)))
+}
+
+/// Publishes the job's records in file order, which keeps the messages of
+/// each ordering key in order. Once a message fails, the later ones with
+/// the same ordering key are skipped rather than published out of order.
+fn spawn_publish(topic: Topic, job: BulkPublishJob, stop: watch::Receiver<bool>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let (lines, mut results) = match load(&job).await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                let progress = BulkProgress {
+                    error: Some(e),
+                    done: true,
+                    ..Default::default()
+                };
+                send_event(BulkPublishEvent::Progress(progress).into()).await;
+                return;
+            }
+        };
+        send_event(BulkPublishEvent::Loaded(lines.len()).into()).await;
+        let mut publisher = topic.new_publisher(Some(PublisherConfig {
+            workers: job.concurrency.min(8),
+            ..Default::default()
+        }));
+        let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+        let feeder = feed(publisher.clone(), job, lines, stop, result_tx);
+
+        let mut progress = BulkProgress::default();
+        let mut reported = Instant::now();
+        // The results are collected as they come in, until every sender,
+        // including the feeder's, is gone.
+        let collector = async {
+            while let Some((line, result)) = result_rx.recv().await {
+                let entry = match &result {
+                    Ok(id) => json!({ "line": line, "message_id": id }),
+                    Err(e) => json!({ "line": line, "error": e }),
+                };
+                let _ = writeln!(results, "{entry}");
+                match result {
+                    Ok(_) => progress.published += 1,
+                    Err(e) => {
+                        progress.failed += 1;
+                        progress.failures.push((line, e));
+                    }
+                }
+                if reported.elapsed() >= REPORT_INTERVAL {
+                    let report = BulkProgress {
+                        failures: std::mem::take(&mut progress.failures),
+                        ..progress.clone()
+                    };
+                    send_event(BulkPublishEvent::Progress(report).into()).await;
+                    reported = Instant::now();
+                }
+            }
+        };
+        tokio::join!(feeder, collector);
+        let _ = results.flush();
+        publisher.shutdown().await;
+        progress.done = true;
+        send_event(BulkPublishEvent::Progress(progress).into()).await;
+    })
+}
+
+/// The line a result is for, and the message ID or the error.
+type PublishResult = (usize, Result<String, String>);
+
+/// Stops early, leaving the messages already sent to finish, once `stop`
+/// is set.
+async fn feed(
+    publisher: Publisher,
+    job: BulkPublishJob,
+    lines: Vec<records::Line>,
+    mut stop: watch::Receiver<bool>,
+    results: mpsc::UnboundedSender<PublishResult>,
+) {
+    let permits = Arc::new(Semaphore::new(job.concurrency));
+    let mut ticker = job.period.map(|period| {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        ticker
+    });
+    // Ordering keys by the first line that failed to publish with them.
+    let failed_keys: Arc<Mutex<HashMap<String, usize>>> = Arc::default();
+    for (line, record) in lines {
+        if *stop.borrow() {
+            return;
+        }
+        let Record {
+            data,
+            attributes,
+            ordering_key,
+        } = match record {
+            Ok(record) => record,
+            Err(e) => {
+                let _ = results.send((line, Err(e)));
+                continue;
+            }
+        };
+        if job.paused_keys.contains(&ordering_key) {
+            let skipped =
+                format!("Skipped, ordering key '{ordering_key}' is paused in the composer");
+            let _ = results.send((line, Err(skipped)));
+            continue;
+        }
+        if let Some(first) = failed_keys.lock().unwrap().get(&ordering_key) {
+            let skipped = format!("Skipped, line {first} failed with the same ordering key");
+            let _ = results.send((line, Err(skipped)));
+            continue;
+        }
+        if let Some(ticker) = ticker.as_mut() {
+            tokio::select! {
+                _ = ticker.tick() => {}
+                _ = stop.wait_for(|stop| *stop) => return,
+            }
+        }
+        let permit = tokio::select! {
+            permit = permits.clone().acquire_owned() => permit,
+            _ = stop.wait_for(|stop| *stop) => return,
+        };
+        let Ok(permit) = permit else {
+            return;
+        };
+        let awaiter = publisher
+            .publish(PubsubMessage {
+                data,
+                attributes,
+                ordering_key: ordering_key.clone(),
+                ..Default::default()
+            })
+            .await;
+        let results = results.clone();
+        let failed_keys = failed_keys.clone();
+        tokio::spawn(async move {
+            let result = awaiter.get().await.map_err(|e| e.message().to_string());
+            drop(permit);
+            if result.is_err() && !ordering_key.is_empty() {
+                failed_keys
+                    .lock()
+                    .unwrap()
+                    .entry(ordering_key)
+                    .or_insert(line);
+            }
+            let _ = results.send((line, result));
+        });
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum BulkPublishEvent {
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Start,
+    /// The file was read and has this many records.
+    Loaded(usize),
+    Progress(BulkProgress),
+    Cancel,
+}
+
+impl From<BulkPublishEvent> for AppEvent {
+    fn from(event: BulkPublishEvent) -> Self {
+        AppEvent::BulkPublish(event)
+    }
+}
+
+impl From<FormEvent> for BulkPublishEvent {
+    fn from(event: FormEvent) -> Self {
+        BulkPublishEvent::Form(event)
+    }
+}
+
+pub fn on_event(
+    state: &mut Pubsub,
+    composer: &Composer,
+    event: BulkPublishEvent,
+) -> Option<AppEvent> {
+    let bulk = &mut state.bulk;
+    match event {
+        BulkPublishEvent::Open(topic) => {
+            bulk.topic = Some(topic);
+            bulk.error = None;
+            if bulk.form.get("rate").is_empty() {
+                bulk.form.set("rate", DEFAULT_RATE.to_string());
+            }
+            if bulk.form.get("concurrency").is_empty() {
+                bulk.form
+                    .set("concurrency", DEFAULT_CONCURRENCY.to_string());
+            }
+            Some(BulkPublishEvent::Form(FormEvent::Focus(Some("file".into()))).into())
+        }
+        BulkPublishEvent::Close => {
+            bulk.topic = None;
+            bulk.form.focused = None;
+            None
+        }
+        BulkPublishEvent::Form(e) => form::on_event(&mut bulk.form, e)
+            .map(BulkPublishEvent::from)
+            .map(AppEvent::from),
+        BulkPublishEvent::Start => match bulk.job(composer) {
+            Ok(job) => {
+                bulk.error = None;
+                bulk.form.focused = None;
+                Some(PubsubEvent::StartBulkPublish(Box::new(job)).into())
+            }
+            Err(e) => {
+                bulk.error = Some(e);
+                None
+            }
+        },
+        BulkPublishEvent::Loaded(total) => {
+            bulk.total = total;
+            None
+        }
+        BulkPublishEvent::Progress(progress) => {
+            bulk.add_progress(progress);
+            if bulk.progress.done {
+                bulk.task = None;
+                bulk.finished = Some(bulk.started.elapsed());
+                let file = bulk.file.as_deref().map(Path::display);
+                let file = file.map(|f| f.to_string()).unwrap_or_default();
+                if let Some(e) = bulk.progress.error.clone() {
+                    state.status.info = Some(format!("Failed to publish from {file}: {e}"));
+                    bulk.error = Some(e);
+                    return None;
+                }
+                state.status.info = Some(format!(
+                    "{} {} of {} messages from {}, {} failed",
+                    match bulk.cancelled {
+                        true => "Cancelled after publishing",
+                        false => "Published",
+                    },
+                    bulk.progress.published,
+                    bulk.total,
+                    file,
+                    bulk.progress.failed
+                ));
+            }
+            None
+        }
+        BulkPublishEvent::Cancel => {
+            bulk.stop();
+            state.status.info =
+                Some("Cancelling, waiting for the messages already sent".to_string());
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &BulkPublish, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(BulkPublishEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter if !state.is_running() => handled(BulkPublishEvent::Start.into()),
+        Char('x') if state.is_running() => handled(BulkPublishEvent::Cancel.into()),
+        Esc => handled(BulkPublishEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to publish, Esc to close";
+const RUNNING_HELP: &str = "Publishing continues when closed. x to cancel, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &BulkPublish, f: &mut Frame, area: Rect) {
+    let topic = state.topic.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Publish a file to {topic}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area, gauge_area, failures_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(12),
+        Constraint::Length(3),
+        Constraint::Min(0),
+    ])
+    .margin(1)
+    .areas(area);
+    let mut help = vec![
+        Line::from(match (state.form.is_editing(), state.is_running()) {
+            (true, _) => EDITING_HELP,
+            (false, true) => RUNNING_HELP,
+            (false, false) => VIEWING_HELP,
+        })
+        .fg(Color::Gray),
+    ];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+
+    let Some(file) = &state.file else {
+        return;
+    };
+    let progress = &state.progress;
+    let done = progress.published + progress.failed;
+    let elapsed = state.finished.unwrap_or(state.started.elapsed());
+    let rate = done as f64 / elapsed.as_secs_f64().max(0.001);
+    let status = match (state.is_running(), state.cancelled) {
+        (true, false) if state.total == 0 => "reading",
+        (true, false) => "publishing",
+        (true, true) => "cancelling",
+        (false, true) => "cancelled",
+        (false, false) => "done",
+    };
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(format!("{} ({status})", file.display()))
+                .borders(Borders::ALL),
+        )
+        .gauge_style(Style::default().fg(Color::LightGreen))
+        .ratio((done as f64 / state.total.max(1) as f64).min(1.0))
+        .label(format!(
+            "{done}/{} sent, {} failed, {rate:.1} msg/s",
+            state.total, progress.failed
+        ));
+    f.render_widget(gauge, gauge_area);
+
+    let mut lines: Vec<Line> = progress
+        .failures
+        .iter()
+        .map(|(line, error)| Line::from(format!("line {line}: {error}")).fg(Color::LightRed))
+        .collect();
+    if let Some(results) = &state.results {
+        lines.insert(
+            0,
+            Line::from(format!("Results are written to {}", results.display())).fg(Color::Gray),
+        );
+    }
+    let failures = Paragraph::new(Text::from(lines))
+        .block(Block::default().title("Failures").borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    f.render_widget(failures, failures_area);
+}
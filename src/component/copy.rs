@@ -0,0 +1,727 @@
+use crate::{
+    component::{
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::{send_event, AppEvent},
+    input::{handled, handled_empty, InputHandled},
+    util::{matches_pattern, parse_key_values, parse_rate, short_name},
+};
+use futures::future::join_all;
+use google_cloud_gax::{
+    conn::{Channel, Environment},
+    create_request,
+    grpc::Status,
+};
+use google_cloud_googleapis::pubsub::v1::{
+    subscriber_client::SubscriberClient, ModifyAckDeadlineRequest, PubsubMessage,
+};
+use google_cloud_pubsub::{
+    client::{Client, ClientConfig},
+    subscription::Subscription,
+    topic::Topic,
+};
+use google_cloud_token::NopeTokenSourceProvider;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{interval, sleep, timeout, MissedTickBehavior},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// How attributes are changed on the way to the destination. Keys are
+/// stripped first, then renamed, then set.
+#[derive(Debug, Clone, Default)]
+pub struct AttributeRewrite {
+    /// Key patterns, where `*` matches anything.
+    pub strip: Vec<String>,
+    pub rename: HashMap<String, String>,
+    pub set: HashMap<String, String>,
+}
+
+impl AttributeRewrite {
+    /// Attributes starting with `goog` are set by Pub/Sub and would be
+    /// rejected when publishing, so they are always dropped.
+    pub fn apply(&self, attributes: HashMap<String, String>) -> HashMap<String, String> {
+        let mut rewritten: HashMap<String, String> = attributes
+            .into_iter()
+            .filter(|(k, _)| !k.starts_with("goog"))
+            .filter(|(k, _)| !self.strip.iter().any(|p| matches_pattern(p, k)))
+            .map(|(k, v)| (self.rename.get(&k).cloned().unwrap_or(k), v))
+            .collect();
+        rewritten.extend(self.set.clone());
+        rewritten
+    }
+}
+
+/// Another Pub/Sub service to publish to, such as an emulator while the
+/// source is read from Google Cloud.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+    /// `host:port`
+    pub address: String,
+    pub emulator: bool,
+}
+
+impl Endpoint {
+    /// A config that doesn't depend on `PUBSUB_EMULATOR_HOST`, which is set
+    /// for the main connection when it is to an emulator.
+    fn client_config(&self, project_id: String) -> ClientConfig {
+        let mut config = ClientConfig {
+            project_id: Some(project_id),
+            ..Default::default()
+        };
+        config.environment = match self.emulator {
+            true => Environment::Emulator(self.address.clone()),
+            false => {
+                config.endpoint = self.address.clone();
+                Environment::GoogleCloud(Box::new(NopeTokenSourceProvider {}))
+            }
+        };
+        config
+    }
+}
+
+/// Republishes messages pulled from `source` to `destination`, in another
+/// project if the destination is a full topic name, and over another
+/// connection if `endpoint` is set.
+#[derive(Debug, Clone)]
+pub struct CopyJob {
+    pub source: String,
+    pub destination: String,
+    pub endpoint: Option<Endpoint>,
+    pub rewrite: AttributeRewrite,
+    /// Time between messages, `None` for as fast as possible.
+    pub period: Option<Duration>,
+    /// Whether copied messages are acked on the source. Otherwise they stay
+    /// leased until the copy ends and are then released, so each one is
+    /// delivered to the copy only once.
+    pub ack: bool,
+    /// Whether to keep waiting for new messages rather than stopping once
+    /// the subscription has nothing new.
+    pub mirror: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CopyProgress {
+    pub copied: usize,
+    pub failed: usize,
+    pub last_error: Option<String>,
+    pub done: bool,
+}
+
+/// The copy dialog, opened on a source subscription, and the copy or mirror
+/// started from it.
+pub struct Copier {
+    pub source: Option<String>,
+    pub form: Form,
+    pub error: Option<String>,
+    pub job: Option<CopyJob>,
+    pub progress: CopyProgress,
+    /// Set once stopping was asked for, until the copy has released its
+    /// leases and reported itself done.
+    pub stopping: bool,
+    task: Option<(JoinHandle<()>, watch::Sender<bool>)>,
+}
+
+impl Default for Copier {
+    fn default() -> Self {
+        fn choice(label: &str, value: &str) -> Choice {
+            Choice {
+                label: label.into(),
+                value: value.into(),
+            }
+        }
+        Self {
+            source: None,
+            form: Form::new(vec![
+                Field::Text(TextField::new(
+                    "destination",
+                    "Destination topic (ID, or projects/<project>/topics/<ID> in another project)",
+                )),
+                Field::Text(TextField::new(
+                    "endpoint",
+                    "Destination endpoint (host:port, empty to publish over this connection)",
+                )),
+                Field::Choices(Choices::new(
+                    "endpoint_kind",
+                    "Destination endpoint is",
+                    vec![
+                        choice("Google Cloud", "cloud"),
+                        choice("An emulator", "emulator"),
+                    ],
+                )),
+                Field::Text(TextField::new(
+                    "strip",
+                    "Attributes to strip (comma separated, * matches anything)",
+                )),
+                Field::Text(TextField::new(
+                    "rename",
+                    "Attributes to rename (old=new, comma separated)",
+                )),
+                Field::Text(TextField::new(
+                    "set",
+                    "Attributes to set (key=value, comma separated)",
+                )),
+                Field::Text(TextField::new(
+                    "rate",
+                    "Messages per second (0 for unlimited)",
+                )),
+                Field::Choices(Choices::new(
+                    "ack",
+                    "Source messages",
+                    vec![
+                        choice("Ack once copied", "ack"),
+                        choice("Leave on the subscription", "leave"),
+                    ],
+                )),
+                Field::Choices(Choices::new(
+                    "mode",
+                    "Mode",
+                    vec![
+                        choice("Copy, then stop", "copy"),
+                        choice("Mirror until stopped", "mirror"),
+                    ],
+                )),
+            ]),
+            error: None,
+            job: None,
+            progress: CopyProgress::default(),
+            stopping: false,
+            task: None,
+        }
+    }
+}
+
+const DEFAULT_RATE: &str = "50";
+const BATCH_SIZE: i32 = 100;
+/// How long a pulled batch may take to copy at the rate limit. The leases
+/// of its messages have to outlast it, and the shortest ack deadline is 10s.
+const BATCH_DURATION: Duration = Duration::from_secs(5);
+/// Message IDs remembered to recognise redelivered messages.
+const MAX_SEEN: usize = 100_000;
+const PULL_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a mirror waits before pulling again from an idle subscription.
+const IDLE_WAIT: Duration = Duration::from_secs(1);
+/// How long the leases of messages left on the subscription are extended
+/// by, and how often, while the copy runs.
+const LEASE_SECONDS: i32 = 60;
+const LEASE_REFRESH: Duration = Duration::from_secs(20);
+/// Ack IDs per ModifyAckDeadline request, which is limited in size.
+const LEASE_CHUNK: usize = 1000;
+
+impl Copier {
+    pub fn is_open(&self) -> bool {
+        self.source.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    fn job(&self) -> Result<CopyJob, String> {
+        let source = self.source.clone().ok_or("No subscription selected")?;
+        let destination = self.form.get("destination").trim().to_string();
+        if destination.is_empty() {
+            return Err("Enter the destination topic".to_string());
+        }
+        let endpoint = match self.form.get("endpoint").trim() {
+            "" => None,
+            address => {
+                let valid = address
+                    .rsplit_once(':')
+                    .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+                if !valid {
+                    return Err("The destination endpoint must be host:port".to_string());
+                }
+                Some(Endpoint {
+                    address: address.to_string(),
+                    emulator: self.form.get("endpoint_kind") == "emulator",
+                })
+            }
+        };
+        let strip = self
+            .form
+            .get("strip")
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+        let period = parse_rate(self.form.get("rate"))?;
+        let ack = self.form.get("ack") == "ack";
+        let mirror = self.form.get("mode") == "mirror";
+        if mirror && !ack {
+            return Err(
+                "A mirror has to ack what it copies, or it would pull the same messages again"
+                    .to_string(),
+            );
+        }
+        Ok(CopyJob {
+            source,
+            destination,
+            endpoint,
+            rewrite: AttributeRewrite {
+                strip,
+                rename: parse_key_values(self.form.get("rename"))?,
+                set: parse_key_values(self.form.get("set"))?,
+            },
+            period,
+            ack,
+            mirror,
+        })
+    }
+
+    /// `destination` is `None` when the job publishes over another
+    /// connection, which the copy opens itself.
+    pub fn start(
+        &mut self,
+        source: Subscription,
+        destination: Option<Topic>,
+        leases: SubscriberClient<Channel>,
+        job: CopyJob,
+    ) {
+        self.stop();
+        self.progress = CopyProgress::default();
+        self.stopping = false;
+        let (stop_tx, stop_rx) = watch::channel(false);
+        let handle = spawn_copy(source, destination, leases, job.clone(), stop_rx);
+        self.task = Some((handle, stop_tx));
+        self.job = Some(job);
+    }
+
+    /// Stops pulling. The copy reports itself done once the batch in flight
+    /// is published and the leases it held are released.
+    pub fn stop(&mut self) {
+        if let Some((_, stop)) = &self.task {
+            let _ = stop.send(true);
+            self.stopping = true;
+        }
+    }
+}
+
+/// Message IDs already copied, forgetting the oldest past `MAX_SEEN`.
+#[derive(Default)]
+struct Seen {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl Seen {
+    /// Returns whether the ID is new.
+    fn insert(&mut self, id: &str) -> bool {
+        if !self.ids.insert(id.to_string()) {
+            return false;
+        }
+        self.order.push_back(id.to_string());
+        if self.order.len() > MAX_SEEN {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    fn forget(&mut self, id: &str) {
+        self.ids.remove(id);
+    }
+}
+
+/// How many messages to pull at once, so that a batch is copied well within
+/// its messages' leases at the rate limit.
+fn batch_size(period: Option<Duration>) -> i32 {
+    match period {
+        Some(period) => {
+            let size = BATCH_DURATION.as_secs_f64() / period.as_secs_f64();
+            (size as i32).clamp(1, BATCH_SIZE)
+        }
+        None => BATCH_SIZE,
+    }
+}
+
+/// Sets the ack deadline of `ack_ids`, where 0 releases them to be
+/// delivered again.
+pub async fn modify_leases(
+    client: &mut SubscriberClient<Channel>,
+    subscription: &str,
+    ack_ids: &[String],
+    ack_deadline_seconds: i32,
+) -> Result<(), Status> {
+    for chunk in ack_ids.chunks(LEASE_CHUNK) {
+        let req = ModifyAckDeadlineRequest {
+            subscription: subscription.to_string(),
+            ack_ids: chunk.to_vec(),
+            ack_deadline_seconds,
+        };
+        client
+            .modify_ack_deadline(create_request(format!("subscription={subscription}"), req))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Opens the job's own connection to the destination. A topic ID is taken
+/// to be in the source subscription's project.
+async fn connect_destination(job: &CopyJob, source: &Subscription) -> Result<Topic, String> {
+    let endpoint = job.endpoint.as_ref().ok_or("No destination endpoint")?;
+    let project_id = source
+        .fully_qualified_name()
+        .split('/')
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+    let client = Client::new(endpoint.client_config(project_id))
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {e}", endpoint.address))?;
+    Ok(client.topic(&job.destination))
+}
+
+fn spawn_copy(
+    source: Subscription,
+    destination: Option<Topic>,
+    mut leases: SubscriberClient<Channel>,
+    job: CopyJob,
+    mut stop: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut progress = CopyProgress::default();
+        let destination = match destination {
+            Some(topic) => topic,
+            None => match connect_destination(&job, &source).await {
+                Ok(topic) => topic,
+                Err(e) => {
+                    progress.last_error = Some(e);
+                    progress.done = true;
+                    send_event(CopyEvent::Progress(progress).into()).await;
+                    return;
+                }
+            },
+        };
+        let mut publisher = destination.new_publisher(None);
+        let mut ticker = job.period.map(|period| {
+            let mut ticker = interval(period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            ticker
+        });
+        let batch_size = batch_size(job.period);
+        let subscription = source.fully_qualified_name().to_string();
+        // Messages left on the subscription are held until the copy ends.
+        // Nacking them would have them delivered to the copy again straight
+        // away, and every delivery counts towards a dead-letter policy.
+        let mut held: Vec<String> = Vec::new();
+        let mut extended = Instant::now();
+        // A held message whose lease ran out anyway comes back, so it is
+        // recognised by ID and not copied twice.
+        let mut seen = Seen::default();
+        loop {
+            // The first message of a batch waits for its turn before the
+            // pull, so that none of the batch's lease is spent waiting.
+            if let Some(ticker) = ticker.as_mut() {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = stop.wait_for(|stop| *stop) => break,
+                }
+            }
+            let pulled = tokio::select! {
+                pulled = timeout(PULL_TIMEOUT, source.pull(batch_size, None)) => pulled,
+                _ = stop.wait_for(|stop| *stop) => break,
+            };
+            let received = match pulled {
+                Ok(Ok(received)) => received,
+                Ok(Err(e)) => {
+                    progress.last_error = Some(format!("Failed to pull: {}", e.message()));
+                    break;
+                }
+                Err(_) => Vec::new(),
+            };
+            if received.is_empty() {
+                match job.mirror {
+                    true => {
+                        sleep(IDLE_WAIT).await;
+                        continue;
+                    }
+                    false => break,
+                }
+            }
+            let fresh: Vec<_> = match job.ack {
+                true => received,
+                false => {
+                    let ack_ids: Vec<String> =
+                        received.iter().map(|m| m.ack_id().to_string()).collect();
+                    if let Err(e) =
+                        modify_leases(&mut leases, &subscription, &ack_ids, LEASE_SECONDS).await
+                    {
+                        progress.last_error =
+                            Some(format!("Failed to extend leases: {}", e.message()));
+                    }
+                    held.extend(ack_ids);
+                    received
+                        .into_iter()
+                        .filter(|m| seen.insert(&m.message.message_id))
+                        .collect()
+                }
+            };
+            let mut awaiters = Vec::with_capacity(fresh.len());
+            for (i, received) in fresh.iter().enumerate() {
+                if let Some(ticker) = ticker.as_mut().filter(|_| i > 0) {
+                    ticker.tick().await;
+                }
+                let message = &received.message;
+                let awaiter = publisher
+                    .publish(PubsubMessage {
+                        data: message.data.clone(),
+                        attributes: job.rewrite.apply(message.attributes.clone()),
+                        ordering_key: message.ordering_key.clone(),
+                        ..Default::default()
+                    })
+                    .await;
+                awaiters.push(awaiter.get());
+            }
+            let mut ack_ids = Vec::new();
+            for (received, result) in fresh.iter().zip(join_all(awaiters).await) {
+                match result {
+                    Ok(_) => {
+                        progress.copied += 1;
+                        ack_ids.push(received.ack_id().to_string());
+                    }
+                    Err(e) => {
+                        progress.failed += 1;
+                        progress.last_error = Some(e.message().to_string());
+                        // Copied again if it is delivered again.
+                        seen.forget(&received.message.message_id);
+                    }
+                }
+            }
+            // Messages that failed to publish aren't acked, so they are
+            // delivered again.
+            if job.ack && !ack_ids.is_empty() {
+                if let Err(e) = source.ack(ack_ids).await {
+                    progress.last_error = Some(format!("Failed to ack: {}", e.message()));
+                }
+            }
+            if !held.is_empty() && extended.elapsed() >= LEASE_REFRESH {
+                if let Err(e) =
+                    modify_leases(&mut leases, &subscription, &held, LEASE_SECONDS).await
+                {
+                    progress.last_error = Some(format!("Failed to extend leases: {}", e.message()));
+                }
+                extended = Instant::now();
+            }
+            send_event(CopyEvent::Progress(progress.clone()).into()).await;
+        }
+        if let Err(e) = modify_leases(&mut leases, &subscription, &held, 0).await {
+            progress.last_error = Some(format!("Failed to release leases: {}", e.message()));
+        }
+        publisher.shutdown().await;
+        progress.done = true;
+        send_event(CopyEvent::Progress(progress).into()).await;
+    })
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum CopyEvent {
+    /// Opens the dialog on a source subscription.
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Start,
+    Progress(CopyProgress),
+    Stop,
+}
+
+impl From<CopyEvent> for AppEvent {
+    fn from(event: CopyEvent) -> Self {
+        AppEvent::Copy(event)
+    }
+}
+
+impl From<FormEvent> for CopyEvent {
+    fn from(event: FormEvent) -> Self {
+        CopyEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: CopyEvent) -> Option<AppEvent> {
+    let copier = &mut state.copier;
+    match event {
+        CopyEvent::Open(source) => {
+            copier.source = Some(source);
+            copier.error = None;
+            for (name, default) in [
+                ("rate", DEFAULT_RATE),
+                ("endpoint_kind", "cloud"),
+                ("ack", "ack"),
+                ("mode", "copy"),
+            ] {
+                if copier.form.get(name).is_empty() {
+                    copier.form.set(name, default.to_string());
+                }
+            }
+            Some(CopyEvent::Form(FormEvent::Focus(Some("destination".into()))).into())
+        }
+        CopyEvent::Close => {
+            copier.source = None;
+            copier.form.focused = None;
+            None
+        }
+        CopyEvent::Form(e) => form::on_event(&mut copier.form, e)
+            .map(CopyEvent::from)
+            .map(AppEvent::from),
+        CopyEvent::Start => match copier.job() {
+            Ok(job) => {
+                copier.error = None;
+                copier.form.focused = None;
+                Some(PubsubEvent::StartCopy(Box::new(job)).into())
+            }
+            Err(e) => {
+                copier.error = Some(e);
+                None
+            }
+        },
+        CopyEvent::Progress(progress) => {
+            copier.progress = progress;
+            if copier.progress.done {
+                copier.task = None;
+                state.status.info = Some(match &copier.progress.last_error {
+                    Some(e) if copier.progress.copied == 0 => format!("Copy stopped: {e}"),
+                    _ => format!(
+                        "Copied {} messages, {} failed",
+                        copier.progress.copied, copier.progress.failed
+                    ),
+                });
+            }
+            None
+        }
+        CopyEvent::Stop => {
+            copier.stop();
+            state.status.info = Some(format!(
+                "Stopping the copy after {} messages",
+                copier.progress.copied
+            ));
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Copier, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(CopyEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter if !state.is_running() => handled(CopyEvent::Start.into()),
+        Char('x') if state.is_running() => handled(CopyEvent::Stop.into()),
+        Esc => handled(CopyEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to start, Esc to close";
+const RUNNING_HELP: &str = "Copying continues when closed. x to stop, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+/// One-line summary for the Subscriptions title while a copy is running.
+pub fn summary(state: &Copier) -> Option<String> {
+    let job = state.job.as_ref().filter(|_| state.is_running())?;
+    let verb = match job.mirror {
+        true => "mirroring",
+        false => "copying",
+    };
+    Some(format!(
+        "● {verb} {} → {}, {} copied",
+        short_name(&job.source),
+        short_name(&job.destination),
+        state.progress.copied
+    ))
+}
+
+pub fn draw(state: &Copier, f: &mut Frame, area: Rect) {
+    let source = state.source.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Copy messages from {source}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area, progress_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(0),
+        Constraint::Length(2),
+    ])
+    .margin(1)
+    .areas(area);
+    let mut help = vec![
+        Line::from(match (state.form.is_editing(), state.is_running()) {
+            (true, _) => EDITING_HELP,
+            (false, true) => RUNNING_HELP,
+            (false, false) => VIEWING_HELP,
+        })
+        .fg(Color::Gray),
+    ];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+
+    let Some(job) = &state.job else {
+        return;
+    };
+    let progress = &state.progress;
+    let status = match (state.is_running(), progress.done) {
+        (true, _) if state.stopping => "stopping",
+        (true, _) => "running",
+        (false, true) => "done",
+        (false, false) => "stopped",
+    };
+    let destination = match &job.endpoint {
+        Some(endpoint) => format!("{} at {}", job.destination, endpoint.address),
+        None => job.destination.clone(),
+    };
+    let mut lines = vec![Line::from(format!(
+        "{} → {destination} ({status}): {} copied, {} failed",
+        short_name(&job.source),
+        progress.copied,
+        progress.failed
+    ))];
+    if let Some(error) = &progress.last_error {
+        lines.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(lines)), progress_area);
+}
@@ -0,0 +1,516 @@
+use crate::{
+    component::{
+        messages::Messages,
+        pubsub::PubsubEvent,
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    filter,
+    input::{handled, handled_empty, InputHandled},
+    util::{parse_duration, parse_key_values, short_name, validate_labels, validate_resource_id},
+};
+use google_cloud_googleapis::pubsub::v1::{
+    DeadLetterPolicy, ExpirationPolicy, PushConfig, RetryPolicy,
+};
+use google_cloud_pubsub::subscription::SubscriptionConfig;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use std::time::Duration;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone, Default)]
+pub struct NewSubscription {
+    pub id: String,
+    pub topic: String,
+    pub config: SubscriptionConfig,
+}
+
+/// A multi-step form for creating a subscription on a topic. Each step is
+/// validated before moving on to the next one.
+pub struct CreateSubscription {
+    pub topic: Option<String>,
+    pub step: usize,
+    pub steps: Vec<Form>,
+    pub error: Option<String>,
+}
+
+const STEP_TITLES: &[&str] = &["Basics", "Delivery", "Failure handling", "Expiration"];
+
+const MIN_ACK_DEADLINE: i32 = 10;
+const MAX_ACK_DEADLINE: i32 = 600;
+const MIN_RETENTION: Duration = Duration::from_secs(10 * 60);
+const MAX_RETENTION: Duration = Duration::from_secs(7 * 86_400);
+const MIN_DELIVERY_ATTEMPTS: i32 = 5;
+const MAX_DELIVERY_ATTEMPTS: i32 = 100;
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+const MIN_EXPIRATION: Duration = Duration::from_secs(86_400);
+
+fn yes_no(name: &str, label: &str) -> Field {
+    Field::Choices(Choices::new(
+        name,
+        label,
+        vec![
+            Choice {
+                label: "Yes".into(),
+                value: "true".into(),
+            },
+            Choice {
+                label: "No".into(),
+                value: "false".into(),
+            },
+        ],
+    ))
+}
+
+impl Default for CreateSubscription {
+    fn default() -> Self {
+        let mut basics = Form::new(vec![
+            Field::Text(TextField::new("id", "Subscription ID")),
+            Field::Text(TextField::new(
+                "ack_deadline",
+                "Ack deadline (seconds, 10-600)",
+            )),
+            Field::Text(TextField::new(
+                "retention",
+                "Message retention (10m-7d; empty for the default of 7d)",
+            )),
+            yes_no("retain_acked", "Retain acked messages"),
+            Field::Text(TextField::new("filter", "Filter (optional)")),
+        ]);
+        basics.set("ack_deadline", "10".to_string());
+        basics.set("retain_acked", "false".to_string());
+
+        let mut delivery = Form::new(vec![
+            Field::Choices(Choices::new(
+                "delivery",
+                "Delivery type",
+                vec![
+                    Choice {
+                        label: "Pull".into(),
+                        value: "pull".into(),
+                    },
+                    Choice {
+                        label: "Push".into(),
+                        value: "push".into(),
+                    },
+                ],
+            )),
+            Field::Text(TextField::new("push_endpoint", "Push endpoint URL")),
+            yes_no("ordering", "Message ordering"),
+            yes_no("exactly_once", "Exactly-once delivery"),
+        ]);
+        delivery.set("delivery", "pull".to_string());
+        delivery.set("ordering", "false".to_string());
+        delivery.set("exactly_once", "false".to_string());
+
+        let failure = Form::new(vec![
+            Field::Text(TextField::new(
+                "dead_letter_topic",
+                "Dead-letter topic (optional)",
+            )),
+            Field::Text(TextField::new(
+                "max_delivery_attempts",
+                "Max delivery attempts (5-100; empty for 5)",
+            )),
+            Field::Text(TextField::new(
+                "min_backoff",
+                "Retry minimum backoff (e.g. 10s; empty to retry immediately)",
+            )),
+            Field::Text(TextField::new(
+                "max_backoff",
+                "Retry maximum backoff (e.g. 10m; empty for 600s)",
+            )),
+        ]);
+
+        let expiration = Form::new(vec![
+            Field::Text(TextField::new(
+                "expiration",
+                "Expire after inactivity (at least 1d, \"never\"; empty for 31d)",
+            )),
+            Field::Text(TextField::new(
+                "labels",
+                "Labels (key=value, comma separated)",
+            )),
+        ]);
+
+        Self {
+            topic: None,
+            step: 0,
+            steps: vec![basics, delivery, failure, expiration],
+            error: None,
+        }
+    }
+}
+
+fn to_proto(duration: Duration) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: duration.as_secs() as i64,
+        nanos: duration.subsec_nanos() as i32,
+    }
+}
+
+fn optional_duration(value: &str) -> Result<Option<Duration>, String> {
+    match value.trim() {
+        "" => Ok(None),
+        value => parse_duration(value).map(Some),
+    }
+}
+
+impl CreateSubscription {
+    pub fn is_open(&self) -> bool {
+        self.topic.is_some()
+    }
+
+    fn form(&self) -> &Form {
+        &self.steps[self.step]
+    }
+
+    fn is_last_step(&self) -> bool {
+        self.step + 1 == self.steps.len()
+    }
+
+    /// Validates one step's fields and copies them into `sub`.
+    fn apply_step(&self, step: usize, sub: &mut NewSubscription) -> Result<(), String> {
+        let form = &self.steps[step];
+        let config = &mut sub.config;
+        match step {
+            0 => {
+                sub.id = form.get("id").trim().to_string();
+                validate_resource_id(&sub.id)?;
+                config.ack_deadline_seconds = form
+                    .get("ack_deadline")
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|s| (MIN_ACK_DEADLINE..=MAX_ACK_DEADLINE).contains(s))
+                    .ok_or("Ack deadline must be between 10 and 600 seconds")?;
+                config.message_retention_duration = optional_duration(form.get("retention"))?;
+                if config
+                    .message_retention_duration
+                    .is_some_and(|d| !(MIN_RETENTION..=MAX_RETENTION).contains(&d))
+                {
+                    return Err("Message retention must be between 10m and 7d".to_string());
+                }
+                config.retain_acked_messages = form.get("retain_acked") == "true";
+                config.filter = form.get("filter").trim().to_string();
+                if !config.filter.is_empty() {
+                    filter::parse(&config.filter).map_err(|e| format!("Filter: {e}"))?;
+                }
+            }
+            1 => {
+                let endpoint = form.get("push_endpoint").trim();
+                if form.get("delivery") == "push" {
+                    if !endpoint.starts_with("https://") && !endpoint.starts_with("http://") {
+                        return Err("Push subscriptions need an http(s) endpoint".to_string());
+                    }
+                    config.push_config = Some(PushConfig {
+                        push_endpoint: endpoint.to_string(),
+                        ..Default::default()
+                    });
+                }
+                config.enable_message_ordering = form.get("ordering") == "true";
+                config.enable_exactly_once_delivery = form.get("exactly_once") == "true";
+                if config.enable_exactly_once_delivery && config.push_config.is_some() {
+                    return Err("Exactly-once delivery is only available for pull".to_string());
+                }
+            }
+            2 => {
+                let dead_letter_topic = form.get("dead_letter_topic").trim();
+                let attempts = form.get("max_delivery_attempts").trim();
+                if !dead_letter_topic.is_empty() {
+                    let max_delivery_attempts = match attempts.is_empty() {
+                        true => MIN_DELIVERY_ATTEMPTS,
+                        false => attempts
+                            .parse()
+                            .ok()
+                            .filter(|n| (MIN_DELIVERY_ATTEMPTS..=MAX_DELIVERY_ATTEMPTS).contains(n))
+                            .ok_or("Max delivery attempts must be between 5 and 100")?,
+                    };
+                    config.dead_letter_policy = Some(DeadLetterPolicy {
+                        dead_letter_topic: dead_letter_topic.to_string(),
+                        max_delivery_attempts,
+                    });
+                } else if !attempts.is_empty() {
+                    return Err("Max delivery attempts needs a dead-letter topic".to_string());
+                }
+
+                let min_backoff = optional_duration(form.get("min_backoff"))?;
+                let max_backoff = optional_duration(form.get("max_backoff"))?;
+                if [min_backoff, max_backoff]
+                    .iter()
+                    .flatten()
+                    .any(|d| *d > MAX_BACKOFF)
+                {
+                    return Err("Retry backoff can be at most 600s".to_string());
+                }
+                if let (Some(min), Some(max)) = (min_backoff, max_backoff) {
+                    if min > max {
+                        return Err("Minimum backoff can't exceed the maximum".to_string());
+                    }
+                }
+                if min_backoff.is_some() || max_backoff.is_some() {
+                    config.retry_policy = Some(RetryPolicy {
+                        minimum_backoff: min_backoff.map(to_proto),
+                        maximum_backoff: max_backoff.map(to_proto),
+                    });
+                }
+            }
+            _ => {
+                config.expiration_policy = match form.get("expiration").trim() {
+                    "" => None,
+                    "never" => Some(ExpirationPolicy { ttl: None }),
+                    value => {
+                        let ttl = parse_duration(value)?;
+                        if ttl < MIN_EXPIRATION {
+                            return Err("Expiration must be at least 1d".to_string());
+                        }
+                        Some(ExpirationPolicy {
+                            ttl: Some(to_proto(ttl)),
+                        })
+                    }
+                };
+                config.labels = parse_key_values(form.get("labels"))?;
+                validate_labels(&config.labels)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn new_subscription(&self) -> Result<NewSubscription, String> {
+        let mut sub = NewSubscription {
+            topic: self.topic.clone().ok_or("No topic selected")?,
+            ..Default::default()
+        };
+        for (step, title) in STEP_TITLES.iter().enumerate() {
+            self.apply_step(step, &mut sub)
+                .map_err(|e| format!("{}: {}", title, e))?;
+        }
+        Ok(sub)
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum CreateSubscriptionEvent {
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Next,
+    Back,
+}
+
+impl From<CreateSubscriptionEvent> for AppEvent {
+    fn from(event: CreateSubscriptionEvent) -> Self {
+        AppEvent::CreateSubscription(event)
+    }
+}
+
+impl From<FormEvent> for CreateSubscriptionEvent {
+    fn from(event: FormEvent) -> Self {
+        CreateSubscriptionEvent::Form(event)
+    }
+}
+
+fn focus_first(state: &CreateSubscription) -> Option<AppEvent> {
+    let first = match state.step {
+        0 => "id",
+        1 => "delivery",
+        2 => "dead_letter_topic",
+        _ => "expiration",
+    };
+    Some(CreateSubscriptionEvent::Form(FormEvent::Focus(Some(first.into()))).into())
+}
+
+pub fn on_event(
+    state: &mut CreateSubscription,
+    event: CreateSubscriptionEvent,
+) -> Option<AppEvent> {
+    match event {
+        CreateSubscriptionEvent::Open(topic) => {
+            *state = CreateSubscription {
+                topic: Some(topic),
+                ..Default::default()
+            };
+            focus_first(state)
+        }
+        CreateSubscriptionEvent::Close => {
+            state.topic = None;
+            None
+        }
+        CreateSubscriptionEvent::Form(e) => form::on_event(&mut state.steps[state.step], e)
+            .map(CreateSubscriptionEvent::from)
+            .map(AppEvent::from),
+        CreateSubscriptionEvent::Next if state.is_last_step() => match state.new_subscription() {
+            Ok(sub) => {
+                state.topic = None;
+                state.error = None;
+                Some(PubsubEvent::CreateSubscription(Box::new(sub)).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+        CreateSubscriptionEvent::Next => {
+            match state.apply_step(state.step, &mut NewSubscription::default()) {
+                Ok(()) => {
+                    state.steps[state.step].focused = None;
+                    state.step += 1;
+                    state.error = None;
+                    focus_first(state)
+                }
+                Err(e) => {
+                    state.error = Some(e);
+                    None
+                }
+            }
+        }
+        CreateSubscriptionEvent::Back => {
+            state.steps[state.step].focused = None;
+            state.step = state.step.saturating_sub(1);
+            state.error = None;
+            focus_first(state)
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &CreateSubscription, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(state.form(), key)
+        .map(CreateSubscriptionEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter => handled(CreateSubscriptionEvent::Next.into()),
+        Esc if state.step == 0 => handled(CreateSubscriptionEvent::Close.into()),
+        Esc => handled(CreateSubscriptionEvent::Back.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str =
+    "↑/↓ to navigate, Spacebar to edit, Enter for the next step, Esc to go back";
+const LAST_STEP_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to create, Esc to go back";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+/// Messages listed under the filter preview.
+const MAX_PREVIEWED: usize = 5;
+
+/// Checks the filter as it's typed, and which pulled messages it would let
+/// through.
+fn filter_preview<'a>(input: &str, messages: &'a Messages) -> Vec<Line<'a>> {
+    let filter = match filter::parse(input.trim()) {
+        Ok(filter) => filter,
+        Err(e) => return vec![Line::from(e).fg(Color::LightRed)],
+    };
+    let Some(subscription) = &messages.subscription else {
+        return vec![Line::from(
+            "The filter is valid. Pull messages to preview which would match.",
+        )
+        .fg(Color::LightGreen)];
+    };
+    let matching: Vec<_> = messages
+        .all
+        .iter()
+        .filter(|m| filter.matches(&m.attributes))
+        .collect();
+    let mut lines = vec![Line::from(format!(
+        "The filter is valid and matches {} of the {} messages pulled from {}",
+        matching.len(),
+        messages.all.len(),
+        short_name(subscription)
+    ))
+    .fg(Color::LightGreen)];
+    lines.extend(matching.iter().take(MAX_PREVIEWED).map(|m| {
+        Line::from(vec![
+            Span::styled(
+                format!("{} ", m.message_id),
+                Style::default().fg(Color::LightCyan),
+            ),
+            Span::raw(m.preview(80)),
+        ])
+    }));
+    if matching.len() > MAX_PREVIEWED {
+        lines.push(
+            Line::from(format!("and {} more", matching.len() - MAX_PREVIEWED)).fg(Color::Gray),
+        );
+    }
+    lines
+}
+
+pub fn draw(state: &CreateSubscription, messages: &Messages, f: &mut Frame, area: Rect) {
+    let topic = state.topic.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!(
+            "Subscribe to {} - step {}/{}: {}",
+            topic,
+            state.step + 1,
+            state.steps.len(),
+            STEP_TITLES[state.step]
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let help = match (state.form().is_editing(), state.is_last_step()) {
+        (true, _) => EDITING_HELP,
+        (false, true) => LAST_STEP_HELP,
+        (false, false) => VIEWING_HELP,
+    };
+    let mut help = vec![Line::from(help).fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+
+    let typed = state.steps[0].typed("filter");
+    if state.step != 0 || typed.trim().is_empty() {
+        form::draw(state.form(), f, fields_area);
+        return;
+    }
+    let [fields_area, preview_area] = Layout::vertical([
+        Constraint::Min(0),
+        Constraint::Length(MAX_PREVIEWED as u16 + 4),
+    ])
+    .areas(fields_area);
+    form::draw(state.form(), f, fields_area);
+    let preview = Paragraph::new(Text::from(filter_preview(typed, messages)))
+        .block(
+            Block::default()
+                .title("Filter preview")
+                .borders(Borders::ALL),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(preview, preview_area);
+}
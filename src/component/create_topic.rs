@@ -0,0 +1,221 @@
+use crate::{
+    component::{
+        pubsub::PubsubEvent,
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::{parse_duration, parse_key_values, validate_labels, validate_resource_id},
+};
+use google_cloud_googleapis::pubsub::v1::Encoding;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::{collections::HashMap, time::Duration};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone)]
+pub struct NewTopic {
+    pub id: String,
+    pub labels: HashMap<String, String>,
+    pub message_retention: Option<Duration>,
+    pub schema: Option<(String, Encoding)>,
+}
+
+pub struct CreateTopic {
+    pub open: bool,
+    pub form: Form,
+    pub error: Option<String>,
+}
+
+const MIN_RETENTION: Duration = Duration::from_secs(10 * 60);
+const MAX_RETENTION: Duration = Duration::from_secs(31 * 86_400);
+
+impl Default for CreateTopic {
+    fn default() -> Self {
+        Self {
+            open: false,
+            form: Form::new(vec![
+                Field::Text(TextField::new("id", "Topic ID")),
+                Field::Text(TextField::new(
+                    "labels",
+                    "Labels (key=value, comma separated)",
+                )),
+                Field::Text(TextField::new(
+                    "retention",
+                    "Message retention (e.g. 7d, 12h; empty for none)",
+                )),
+                Field::Text(TextField::new("schema", "Schema (optional)")),
+                Field::Choices(Choices::new(
+                    "encoding",
+                    "Schema encoding",
+                    vec![
+                        Choice {
+                            label: "JSON".into(),
+                            value: "JSON".into(),
+                        },
+                        Choice {
+                            label: "Binary".into(),
+                            value: "BINARY".into(),
+                        },
+                    ],
+                )),
+            ]),
+            error: None,
+        }
+    }
+}
+
+impl CreateTopic {
+    fn new_topic(&self) -> Result<NewTopic, String> {
+        let id = self.form.get("id").trim().to_string();
+        validate_resource_id(&id)?;
+
+        let labels = parse_key_values(self.form.get("labels"))?;
+        validate_labels(&labels)?;
+
+        let retention = self.form.get("retention").trim();
+        let message_retention = match retention.is_empty() {
+            true => None,
+            false => {
+                let duration = parse_duration(retention)?;
+                if !(MIN_RETENTION..=MAX_RETENTION).contains(&duration) {
+                    return Err("Message retention must be between 10m and 31d".to_string());
+                }
+                Some(duration)
+            }
+        };
+
+        let schema = self.form.get("schema").trim();
+        let schema = match schema.is_empty() {
+            true => None,
+            false => {
+                let encoding = Encoding::from_str_name(self.form.get("encoding"))
+                    .ok_or("Choose a schema encoding")?;
+                Some((schema.to_string(), encoding))
+            }
+        };
+
+        Ok(NewTopic {
+            id,
+            labels,
+            message_retention,
+            schema,
+        })
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum CreateTopicEvent {
+    Open,
+    Close,
+    Form(FormEvent),
+    Submit,
+}
+
+impl From<CreateTopicEvent> for AppEvent {
+    fn from(event: CreateTopicEvent) -> Self {
+        AppEvent::CreateTopic(event)
+    }
+}
+
+impl From<FormEvent> for CreateTopicEvent {
+    fn from(event: FormEvent) -> Self {
+        CreateTopicEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut CreateTopic, event: CreateTopicEvent) -> Option<AppEvent> {
+    match event {
+        CreateTopicEvent::Open => {
+            state.open = true;
+            state.error = None;
+            Some(CreateTopicEvent::Form(FormEvent::Focus(Some("id".into()))).into())
+        }
+        CreateTopicEvent::Close => {
+            state.open = false;
+            state.form.focused = None;
+            None
+        }
+        CreateTopicEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(CreateTopicEvent::from)
+            .map(AppEvent::from),
+        CreateTopicEvent::Submit => match state.new_topic() {
+            Ok(topic) => {
+                state.open = false;
+                state.error = None;
+                Some(PubsubEvent::CreateTopic(topic).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &CreateTopic, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(CreateTopicEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter => handled(CreateTopicEvent::Submit.into()),
+        Esc => handled(CreateTopicEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to create, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &CreateTopic, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .title("Create topic")
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let mut help = vec![Line::from(match state.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+}
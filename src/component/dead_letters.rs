@@ -0,0 +1,547 @@
+use crate::{
+    component::{
+        messages::MessageInfo,
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::hex_view::{self, HexView, HexViewEvent},
+        subscriptions::SubscriptionInfo,
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::short_name,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Enter, Esc, Up},
+        KeyEvent,
+    },
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::{Duration, Instant},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Set by Pub/Sub on messages it forwards to a dead-letter topic.
+pub const DELIVERY_COUNT_ATTRIBUTE: &str = "CloudPubSubDeadLetterSourceDeliveryCount";
+/// The ID of the subscription the message was dead-lettered from, and the
+/// project that subscription is in.
+pub const SOURCE_SUBSCRIPTION_ATTRIBUTE: &str = "CloudPubSubDeadLetterSourceSubscription";
+pub const SOURCE_PROJECT_ATTRIBUTE: &str = "CloudPubSubDeadLetterSourceSubscriptionProject";
+/// The prefix of every attribute Pub/Sub adds when dead-lettering, which are
+/// dropped when redriving.
+const DEAD_LETTER_PREFIX: &str = "CloudPubSubDeadLetter";
+
+const BATCH_SIZE: i32 = 50;
+/// Pulled messages have their leases extended this long before they run
+/// out, so that a redrive doesn't ack expired IDs.
+const LEASE_MARGIN: Duration = Duration::from_secs(5);
+pub const LEASE_EXTENSION_SECONDS: i32 = 60;
+
+/// Messages to publish back to a topic, with their ack IDs on the
+/// dead-letter subscription.
+#[derive(Debug, Clone)]
+pub struct Redrive {
+    pub dead_letter_subscription: String,
+    pub by_topic: BTreeMap<String, Vec<MessageInfo>>,
+    /// Messages whose source subscription isn't known, so they are skipped.
+    pub unknown_source: usize,
+}
+
+impl Redrive {
+    pub fn count(&self) -> usize {
+        self.by_topic.values().map(Vec::len).sum()
+    }
+}
+
+/// Copies the message for republishing, without the attributes Pub/Sub
+/// added when dead-lettering it.
+pub fn original(message: &MessageInfo) -> MessageInfo {
+    let mut original = message.clone();
+    original
+        .attributes
+        .retain(|k, _| !k.starts_with(DEAD_LETTER_PREFIX));
+    original
+}
+
+/// Messages pulled from a subscription of another subscription's
+/// dead-letter topic, held here rather than in the Messages buffer.
+#[derive(Default)]
+pub struct DeadLetters {
+    /// The subscription whose dead letters are shown.
+    pub source: Option<SubscriptionInfo>,
+    /// The subscription on the dead-letter topic they are pulled from.
+    pub subscription: Option<String>,
+    pub all: Vec<MessageInfo>,
+    pub selected: Option<usize>,
+    /// Ack IDs of the marked messages.
+    pub marked: HashSet<String>,
+    /// A redrive waiting to be confirmed, after showing its counts.
+    pub pending: Option<Redrive>,
+    /// Whether a confirmed redrive is still publishing.
+    pub redriving: bool,
+    /// When the leases of the pulled messages run out, at the earliest.
+    pub leased_until: Option<Instant>,
+    pub error: Option<String>,
+    /// The selected message's payload, shown next to the list when toggled.
+    pub hex: HexView,
+    pub show_hex: bool,
+}
+
+impl DeadLetters {
+    pub fn is_open(&self) -> bool {
+        self.source.is_some()
+    }
+
+    fn sync_hex(&mut self) {
+        let selected = self.selected.and_then(|i| self.all.get(i));
+        self.hex
+            .set_data(selected.map(|m| m.data.as_slice()).unwrap_or_default());
+    }
+
+    /// Groups the chosen messages by the topic of the subscription they
+    /// were dead-lettered from.
+    fn plan(&self, all: bool, subscriptions: &[SubscriptionInfo]) -> Result<Redrive, String> {
+        let subscription = self.subscription.clone().ok_or("Nothing pulled yet")?;
+        let chosen: Vec<&MessageInfo> = match (all, self.marked.is_empty()) {
+            (true, _) => self.all.iter().collect(),
+            (false, true) => self
+                .selected
+                .and_then(|i| self.all.get(i))
+                .into_iter()
+                .collect(),
+            (false, false) => self
+                .all
+                .iter()
+                .filter(|m| self.marked.contains(&m.ack_id))
+                .collect(),
+        };
+        if chosen.is_empty() {
+            return Err("There are no messages to redrive".to_string());
+        }
+        let mut redrive = Redrive {
+            dead_letter_subscription: subscription,
+            by_topic: BTreeMap::new(),
+            unknown_source: 0,
+        };
+        for message in chosen {
+            // Without the attribute the message is assumed to come from the
+            // subscription the inspector was opened on.
+            let project = message.attributes.get(SOURCE_PROJECT_ATTRIBUTE);
+            let topic = match message.attributes.get(SOURCE_SUBSCRIPTION_ATTRIBUTE) {
+                Some(id) => subscriptions
+                    .iter()
+                    .find(|s| match project {
+                        Some(project) => s.name == format!("projects/{project}/subscriptions/{id}"),
+                        None => short_name(&s.name) == id,
+                    })
+                    .map(|s| s.topic.clone()),
+                None => self.source.as_ref().map(|s| s.topic.clone()),
+            };
+            match topic {
+                Some(topic) => redrive
+                    .by_topic
+                    .entry(topic)
+                    .or_default()
+                    .push(message.clone()),
+                None => redrive.unknown_source += 1,
+            }
+        }
+        Ok(redrive)
+    }
+
+    fn remove(&mut self, ack_ids: &[String]) {
+        let removed: HashSet<&str> = ack_ids.iter().map(String::as_str).collect();
+        self.all.retain(|m| !removed.contains(m.ack_id.as_str()));
+        for ack_id in ack_ids {
+            self.marked.remove(ack_id);
+        }
+        self.selected = match self.all.is_empty() {
+            true => None,
+            false => Some(self.selected.unwrap_or(0).min(self.all.len() - 1)),
+        };
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum DeadLettersEvent {
+    /// Opens the inspector on a subscription with a dead-letter policy.
+    Open(String),
+    Close,
+    Pull,
+    Got(String, Vec<MessageInfo>),
+    PullFailed(String),
+    Select(Option<usize>),
+    ToggleMark(String),
+    /// Counts what redriving the marked (or selected) messages, or all of
+    /// them, would publish.
+    Plan(bool),
+    Confirm,
+    Cancel,
+    /// The leases of the pulled messages were extended until then, or why
+    /// they couldn't be.
+    Leased(Result<Instant, String>),
+    /// Ack IDs of the redriven messages and, if some failed, why.
+    Redriven(Vec<String>, Option<String>),
+    Hex(HexViewEvent),
+    ToggleHex,
+}
+
+impl From<DeadLettersEvent> for AppEvent {
+    fn from(event: DeadLettersEvent) -> Self {
+        AppEvent::DeadLetters(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: DeadLettersEvent) -> Option<AppEvent> {
+    let chained = on_dead_letters_event(state, event);
+    state.dead_letters.sync_hex();
+    chained
+}
+
+fn on_dead_letters_event(state: &mut Pubsub, event: DeadLettersEvent) -> Option<AppEvent> {
+    let dead_letters = &mut state.dead_letters;
+    match event {
+        DeadLettersEvent::Open(name) => {
+            let source = state.subscriptions.all.iter().find(|s| s.name == name)?;
+            // Any pull subscription on the dead-letter topic will do.
+            let subscription = state
+                .subscriptions
+                .all
+                .iter()
+                .find(|s| {
+                    !source.dead_letter_topic.is_empty() && s.topic == source.dead_letter_topic
+                })
+                .map(|s| s.name.clone());
+            *dead_letters = DeadLetters {
+                source: Some(source.clone()),
+                error: subscription.is_none().then(|| {
+                    format!(
+                        "No subscription on {}, create one to read its messages",
+                        short_name(&source.dead_letter_topic)
+                    )
+                }),
+                subscription,
+                ..Default::default()
+            };
+            Some(DeadLettersEvent::Pull.into())
+        }
+        DeadLettersEvent::Close => {
+            *dead_letters = DeadLetters::default();
+            None
+        }
+        DeadLettersEvent::Pull => {
+            let subscription = dead_letters.subscription.clone()?;
+            Some(PubsubEvent::PullDeadLetters(subscription, BATCH_SIZE).into())
+        }
+        DeadLettersEvent::Got(subscription, messages) => {
+            // The inspector was closed or moved on while pulling.
+            if dead_letters.subscription.as_ref() != Some(&subscription) {
+                let ack_ids = messages.into_iter().map(|m| m.ack_id).collect();
+                return Some(PubsubEvent::Nack(subscription, ack_ids).into());
+            }
+            let deadline = state
+                .subscriptions
+                .all
+                .iter()
+                .find(|s| s.name == subscription)
+                .map_or(10, |s| s.ack_deadline_seconds);
+            let leased_until = Instant::now() + Duration::from_secs(deadline.max(0) as u64);
+            dead_letters.leased_until = Some(match dead_letters.leased_until {
+                Some(until) => until.min(leased_until),
+                None => leased_until,
+            });
+            for message in messages {
+                // A redelivered message replaces its old, expired ack ID.
+                match dead_letters
+                    .all
+                    .iter_mut()
+                    .find(|m| m.message_id == message.message_id)
+                {
+                    Some(known) => {
+                        if dead_letters.marked.remove(&known.ack_id) {
+                            dead_letters.marked.insert(message.ack_id.clone());
+                        }
+                        known.ack_id = message.ack_id;
+                    }
+                    None => dead_letters.all.push(message),
+                }
+            }
+            if dead_letters.selected.is_none() && !dead_letters.all.is_empty() {
+                dead_letters.selected = Some(0);
+            }
+            None
+        }
+        DeadLettersEvent::PullFailed(reason) => {
+            state.status.info = Some(reason);
+            None
+        }
+        DeadLettersEvent::Select(selected) => {
+            dead_letters.selected = selected;
+            None
+        }
+        DeadLettersEvent::ToggleMark(ack_id) => {
+            if !dead_letters.marked.remove(&ack_id) {
+                dead_letters.marked.insert(ack_id);
+            }
+            None
+        }
+        DeadLettersEvent::Plan(_) if dead_letters.redriving => {
+            dead_letters.error = Some("Wait for the redrive to finish".to_string());
+            None
+        }
+        DeadLettersEvent::Plan(all) => {
+            match dead_letters.plan(all, &state.subscriptions.all) {
+                Ok(redrive) => {
+                    dead_letters.error = None;
+                    dead_letters.pending = Some(redrive);
+                }
+                Err(e) => dead_letters.error = Some(e),
+            }
+            None
+        }
+        DeadLettersEvent::Cancel => {
+            dead_letters.pending = None;
+            None
+        }
+        DeadLettersEvent::Confirm => {
+            let redrive = dead_letters.pending.take()?;
+            dead_letters.redriving = true;
+            state.status.info = Some(format!("Redriving {} messages", redrive.count()));
+            Some(PubsubEvent::Redrive(Box::new(redrive)).into())
+        }
+        DeadLettersEvent::Leased(Ok(until)) => {
+            dead_letters.leased_until = Some(until);
+            None
+        }
+        DeadLettersEvent::Leased(Err(e)) => {
+            dead_letters.error = Some(format!(
+                "Failed to extend the leases, pull again before redriving: {e}"
+            ));
+            None
+        }
+        DeadLettersEvent::Redriven(ack_ids, error) => {
+            dead_letters.redriving = false;
+            dead_letters.remove(&ack_ids);
+            dead_letters.error = error.clone();
+            state.status.info = Some(match error {
+                Some(e) => format!("Redrove {} messages, then failed: {e}", ack_ids.len()),
+                None => format!("Redrove {} messages", ack_ids.len()),
+            });
+            None
+        }
+        DeadLettersEvent::Hex(e) => hex_view::on_event(&mut dead_letters.hex, e)
+            .map(DeadLettersEvent::Hex)
+            .map(AppEvent::from),
+        DeadLettersEvent::ToggleHex => {
+            dead_letters.show_hex = !dead_letters.show_hex;
+            None
+        }
+    }
+}
+
+/// Extends the leases of the pulled messages shortly before they run out,
+/// for as long as they are shown.
+pub fn on_tick(state: &mut Pubsub) -> Option<AppEvent> {
+    let dead_letters = &mut state.dead_letters;
+    let subscription = dead_letters.subscription.clone()?;
+    let until = dead_letters.leased_until?;
+    if dead_letters.all.is_empty() || until.saturating_duration_since(Instant::now()) > LEASE_MARGIN
+    {
+        return None;
+    }
+    // Not asked again until the extension is answered.
+    dead_letters.leased_until = None;
+    let ack_ids = dead_letters.all.iter().map(|m| m.ack_id.clone()).collect();
+    Some(PubsubEvent::ExtendDeadLetterLeases(subscription, ack_ids).into())
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &DeadLetters, key: KeyEvent) -> InputHandled<AppEvent> {
+    if state.pending.is_some() {
+        return match key.code {
+            Enter => handled(DeadLettersEvent::Confirm.into()),
+            Esc => handled(DeadLettersEvent::Cancel.into()),
+            _ => handled_empty(),
+        };
+    }
+    if state.show_hex {
+        let hex_handled = hex_view::on_key(&state.hex, key).map(DeadLettersEvent::Hex);
+        if hex_handled.is_handled() {
+            return hex_handled.map(AppEvent::from);
+        }
+    }
+    let count = state.all.len();
+    match key.code {
+        Up if count > 0 => handled(
+            DeadLettersEvent::Select(Some(
+                state
+                    .selected
+                    .map_or(count - 1, |i| (i + count - 1) % count),
+            ))
+            .into(),
+        ),
+        Down if count > 0 => handled(
+            DeadLettersEvent::Select(Some(state.selected.map_or(0, |i| (i + 1) % count))).into(),
+        ),
+        Char('p') => handled(DeadLettersEvent::Pull.into()),
+        Char(' ') => match state.selected.and_then(|i| state.all.get(i)) {
+            Some(message) => handled(DeadLettersEvent::ToggleMark(message.ack_id.clone()).into()),
+            None => handled_empty(),
+        },
+        Char('r') => handled(DeadLettersEvent::Plan(false).into()),
+        Char('R') => handled(DeadLettersEvent::Plan(true).into()),
+        Char('v') if state.selected.is_some() => handled(DeadLettersEvent::ToggleHex.into()),
+        Esc => handled(DeadLettersEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str =
+    "↑/↓ select, p pull, Space mark, r redrive marked, R redrive all pulled, v hex view, Esc close";
+
+pub fn draw(state: &DeadLetters, f: &mut Frame, area: Rect) {
+    let Some(source) = &state.source else {
+        return;
+    };
+    let block = Block::default()
+        .title(format!("Dead letters of {}", short_name(&source.name)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [info_area, list_area, footer_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(3),
+    ])
+    .margin(1)
+    .areas(area);
+
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    let info = vec![
+        field(
+            "Dead-letter topic",
+            format!(
+                "{} (after {} delivery attempts)",
+                short_name(&source.dead_letter_topic),
+                source.max_delivery_attempts
+            ),
+        ),
+        field(
+            "Pulling from",
+            state
+                .subscription
+                .as_deref()
+                .map(short_name)
+                .unwrap_or("-")
+                .to_string(),
+        ),
+        field(
+            "Pulled",
+            format!("{} ({} marked)", state.all.len(), state.marked.len()),
+        ),
+    ];
+    f.render_widget(Paragraph::new(Text::from(info)), info_area);
+
+    let header = Row::new(vec!["", "Deliveries", "Source", "Payload"])
+        .style(Style::default().bold().fg(Color::LightCyan));
+    let rows = state.all.iter().map(|m| {
+        let attribute = |name| m.attributes.get(name).cloned().unwrap_or("-".to_string());
+        Row::new(vec![
+            match state.marked.contains(&m.ack_id) {
+                true => "●".to_string(),
+                false => String::new(),
+            },
+            attribute(DELIVERY_COUNT_ATTRIBUTE),
+            short_name(&attribute(SOURCE_SUBSCRIPTION_ATTRIBUTE)).to_string(),
+            m.preview(60),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(10),
+            Constraint::Percentage(30),
+            Constraint::Min(0),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(Block::default().borders(Borders::ALL));
+    let mut table_state = TableState::default().with_selected(state.selected);
+    match state.show_hex {
+        true => {
+            let [table_area, hex_area] = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(list_area);
+            f.render_stateful_widget(table, table_area, &mut table_state);
+            let block = Block::default().title("Payload").borders(Borders::ALL);
+            let inner = block.inner(hex_area);
+            f.render_widget(block, hex_area);
+            hex_view::draw(&state.hex, f, inner);
+        }
+        false => f.render_stateful_widget(table, list_area, &mut table_state),
+    }
+
+    let footer = match (&state.pending, &state.error) {
+        (Some(redrive), _) => {
+            let topics: Vec<String> = redrive
+                .by_topic
+                .iter()
+                .map(|(topic, messages)| format!("{} to {}", messages.len(), short_name(topic)))
+                .collect();
+            let mut lines = vec![Line::from(format!(
+                "Dry run: {} messages would be published, {}",
+                redrive.count(),
+                topics.join(", ")
+            ))
+            .fg(Color::LightYellow)];
+            if redrive.unknown_source > 0 {
+                lines.push(
+                    Line::from(format!(
+                        "{} skipped, their source subscription isn't known",
+                        redrive.unknown_source
+                    ))
+                    .fg(Color::LightRed),
+                );
+            }
+            lines.push(Line::from("Enter to redrive, Esc to cancel").fg(Color::Gray));
+            lines
+        }
+        (None, Some(error)) => vec![
+            Line::from(error.as_str()).fg(Color::LightRed),
+            Line::from(HELP).fg(Color::Gray),
+        ],
+        (None, None) => vec![Line::from(HELP).fg(Color::Gray)],
+    };
+    f.render_widget(
+        Paragraph::new(Text::from(footer)).wrap(Wrap { trim: false }),
+        footer_area,
+    );
+}
@@ -9,7 +9,7 @@ use std::sync::Mutex;
 
 use crate::app::App;
 use crate::event::AppEvent;
-use crate::input::{handled, InputHandled, IntoHandled};
+use crate::input::{handled, InputHandled};
 use once_cell::sync::Lazy;
 
 pub static DEBUG_LOGS: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
@@ -24,6 +24,7 @@ pub fn debug_log<S: Into<String>>(msg: S) {
     logs.push_back(msg.into());
 }
 
+#[allow(dead_code)]
 pub fn debug_logs_clear() {
     let mut logs = DEBUG_LOGS.lock().unwrap();
     logs.clear();
@@ -59,7 +60,7 @@ pub fn toggle_debug_logs() -> InputHandled<AppEvent> {
 }
 
 pub fn on_tick(state: &mut App) -> Option<AppEvent> {
-    if state.ticks % 5 != 0 {
+    if !state.ticks.is_multiple_of(5) {
         return None;
     }
     state.debug_logs.drain_logs();
@@ -0,0 +1,353 @@
+use crate::{
+    codec::{avro, protobuf, Decoded},
+    component::{
+        messages::MessageInfo,
+        pubsub::Pubsub,
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+        topics::TopicSchema,
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::{matches_pattern, short_name},
+};
+use google_cloud_googleapis::pubsub::v1::schema::Type;
+use prost_reflect::MessageDescriptor;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Decodes binary protobuf payloads of the topics matching `pattern`.
+pub struct ProtoDecoder {
+    pub pattern: String,
+    /// The descriptor set or `.proto` directory the message was loaded from.
+    pub source: String,
+    pub message: MessageDescriptor,
+}
+
+/// Set by Pub/Sub on messages published to a topic with a schema.
+pub const SCHEMA_REVISION_ATTRIBUTE: &str = "googclient_schemarevisionid";
+
+/// Payload decoders registered for this session, and the dialog that
+/// registers them. Topics with an Avro schema and binary encoding are
+/// decoded without registering anything.
+pub struct Decoders {
+    pub protobuf: Vec<ProtoDecoder>,
+    /// Schema settings of the topics seen so far, `None` if a topic has none.
+    pub topic_schemas: HashMap<String, Option<TopicSchema>>,
+    /// Schema revisions that could not be fetched, so they aren't retried.
+    pub unavailable: HashSet<String>,
+    /// Topics whose schema settings could not be fetched, so decoding doesn't
+    /// retry them. Publishing still does, as it mustn't skip validation.
+    pub unavailable_topics: HashSet<String>,
+    pub open: bool,
+    pub form: Form,
+    pub error: Option<String>,
+}
+
+impl Default for Decoders {
+    fn default() -> Self {
+        Self {
+            protobuf: Vec::new(),
+            topic_schemas: HashMap::new(),
+            unavailable: HashSet::new(),
+            unavailable_topics: HashSet::new(),
+            open: false,
+            form: Form::new(vec![
+                Field::Text(TextField::new(
+                    "pattern",
+                    "Topic name or pattern (* matches anything)",
+                )),
+                Field::Text(TextField::new(
+                    "source",
+                    "Descriptor set file or .proto directory (empty to remove)",
+                )),
+                Field::Text(TextField::new(
+                    "message",
+                    "Message type, e.g. shop.v1.Order",
+                )),
+            ]),
+            error: None,
+        }
+    }
+}
+
+impl Decoders {
+    /// An exact topic name wins over a pattern, otherwise the first
+    /// registered pattern that matches is used.
+    fn find(&self, topic: &str) -> Option<&ProtoDecoder> {
+        self.protobuf
+            .iter()
+            .find(|d| d.pattern == topic || d.pattern == short_name(topic))
+            .or_else(|| {
+                self.protobuf
+                    .iter()
+                    .find(|d| matches_pattern(&d.pattern, topic))
+            })
+    }
+
+    pub fn decode(&self, topic: &str, data: &[u8]) -> Option<Decoded> {
+        let decoder = self.find(topic)?;
+        Some(Decoded {
+            kind: decoder.message.full_name().to_string(),
+            result: protobuf::decode(&decoder.message, data),
+        })
+    }
+
+    /// Registers the decoder described by the form, replacing any other for
+    /// the same pattern. An empty source removes the pattern's decoder.
+    fn register(&mut self) -> Result<String, String> {
+        let pattern = self.form.get("pattern").trim().to_string();
+        if pattern.is_empty() {
+            return Err("Enter a topic name or pattern".to_string());
+        }
+        let source = self.form.get("source").trim().to_string();
+        if source.is_empty() {
+            let before = self.protobuf.len();
+            self.protobuf.retain(|d| d.pattern != pattern);
+            return match self.protobuf.len() < before {
+                true => Ok(format!("Removed the decoder for {pattern}")),
+                false => Err(format!("No decoder is registered for {pattern}")),
+            };
+        }
+        let type_name = self.form.get("message").trim().to_string();
+        if type_name.is_empty() {
+            return Err("Enter the message type to decode".to_string());
+        }
+        let pool = protobuf::load_descriptors(Path::new(&source))?;
+        let message = pool
+            .get_message_by_name(&type_name)
+            .ok_or_else(|| format!("{type_name} is not defined in {source}"))?;
+        self.protobuf.retain(|d| d.pattern != pattern);
+        self.protobuf.push(ProtoDecoder {
+            pattern: pattern.clone(),
+            source,
+            message,
+        });
+        Ok(format!("Decoding {pattern} as {type_name}"))
+    }
+}
+
+/// The cache key of the schema revision `message` was published with,
+/// falling back to the newest revision the topic allows.
+pub fn schema_key(schema: &TopicSchema, message: &MessageInfo) -> String {
+    let revision = message
+        .attributes
+        .get(SCHEMA_REVISION_ATTRIBUTE)
+        .unwrap_or(&schema.last_revision_id);
+    match revision.is_empty() {
+        true => schema.name.clone(),
+        false => format!("{}@{}", schema.name, revision),
+    }
+}
+
+pub fn subscription_topic<'a>(pubsub: &'a Pubsub, subscription: &str) -> Option<&'a str> {
+    pubsub
+        .subscriptions
+        .all
+        .iter()
+        .find(|s| s.name == subscription)
+        .map(|s| s.topic.as_str())
+}
+
+/// Decodes `messages` pulled from `subscription` with a registered decoder
+/// for its topic, or with the topic's Avro schema. Returns `true` if the
+/// topic's schema settings or a schema revision still need fetching.
+pub fn decode_messages(pubsub: &Pubsub, subscription: &str, messages: &mut [MessageInfo]) -> bool {
+    let Some(topic) = subscription_topic(pubsub, subscription) else {
+        return false;
+    };
+    let decoders = &pubsub.decoders;
+    let settings = decoders.topic_schemas.get(topic);
+    let schema = settings
+        .and_then(Option::as_ref)
+        .filter(|s| s.encoding == "BINARY");
+    let mut incomplete = settings.is_none() && !decoders.unavailable_topics.contains(topic);
+    let mut compiled = HashMap::new();
+    for message in messages {
+        message.decoded = decoders.decode(topic, &message.data);
+        let Some(schema) = schema.filter(|_| message.decoded.is_none()) else {
+            continue;
+        };
+        let key = schema_key(schema, message);
+        let Some(info) = pubsub.schema_cache.get(&key) else {
+            incomplete |= !decoders.unavailable.contains(&key);
+            continue;
+        };
+        if info.kind != Type::Avro {
+            continue;
+        }
+        let codec = compiled
+            .entry(key.clone())
+            .or_insert_with(|| avro::compile(&info.definition));
+        message.decoded = Some(Decoded {
+            kind: format!("Avro {}", short_name(&key)),
+            result: codec
+                .as_ref()
+                .map_err(Clone::clone)
+                .and_then(|codec| avro::decode(codec, &message.data)),
+        });
+    }
+    incomplete
+}
+
+/// Decodes the buffered messages again, after decoders or schemas changed.
+pub fn redecode(pubsub: &mut Pubsub) -> bool {
+    let Some(subscription) = pubsub.messages.subscription.clone() else {
+        return false;
+    };
+    let mut messages = std::mem::take(&mut pubsub.messages.all);
+    let incomplete = decode_messages(pubsub, &subscription, &mut messages);
+    pubsub.messages.all = messages;
+    pubsub.messages.sync_payload();
+    incomplete
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum DecodersEvent {
+    /// Opens the dialog, prefilled with a topic name.
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Submit,
+}
+
+impl From<DecodersEvent> for AppEvent {
+    fn from(event: DecodersEvent) -> Self {
+        AppEvent::Decoders(event)
+    }
+}
+
+impl From<FormEvent> for DecodersEvent {
+    fn from(event: FormEvent) -> Self {
+        DecodersEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: DecodersEvent) -> Option<AppEvent> {
+    let decoders = &mut state.decoders;
+    match event {
+        DecodersEvent::Open(topic) => {
+            decoders.open = true;
+            decoders.error = None;
+            decoders.form.set("pattern", topic);
+            Some(DecodersEvent::Form(FormEvent::Focus(Some("source".into()))).into())
+        }
+        DecodersEvent::Close => {
+            decoders.open = false;
+            decoders.form.focused = None;
+            None
+        }
+        DecodersEvent::Form(e) => form::on_event(&mut decoders.form, e)
+            .map(DecodersEvent::from)
+            .map(AppEvent::from),
+        DecodersEvent::Submit => {
+            match decoders.register() {
+                Ok(info) => {
+                    decoders.open = false;
+                    decoders.error = None;
+                    decoders.form.focused = None;
+                    state.status.info = Some(info);
+                }
+                Err(e) => {
+                    decoders.error = Some(e);
+                    return None;
+                }
+            }
+            redecode(state);
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Decoders, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(DecodersEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter => handled(DecodersEvent::Submit.into()),
+        Esc => handled(DecodersEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to register, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &Decoders, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .title("Protobuf decoders")
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area, registered_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(9),
+        Constraint::Min(0),
+    ])
+    .margin(1)
+    .areas(area);
+    let mut help = vec![Line::from(match state.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+
+    let lines: Vec<Line> = match state.protobuf.is_empty() {
+        true => vec![Line::from("No decoders registered").fg(Color::Gray)],
+        false => state
+            .protobuf
+            .iter()
+            .map(|d| {
+                Line::from(vec![
+                    Span::styled(d.pattern.clone(), Style::default().fg(Color::LightCyan)),
+                    Span::raw(format!(" → {} ", d.message.full_name())),
+                    Span::styled(format!("({})", d.source), Style::default().fg(Color::Gray)),
+                ])
+            })
+            .collect(),
+    };
+    let registered = Paragraph::new(Text::from(lines))
+        .block(Block::default().title("Registered").borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    f.render_widget(registered, registered_area);
+}
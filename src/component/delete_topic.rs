@@ -0,0 +1,199 @@
+use crate::{
+    component::{
+        pubsub::PubsubEvent,
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+        topics::TopicDetails,
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::short_name,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Confirmation for deleting a topic. Outside the emulator the topic's ID
+/// has to be typed before Enter does anything.
+pub struct DeleteTopic {
+    pub topic: Option<String>,
+    pub require_name: bool,
+    pub form: Form,
+    pub error: Option<String>,
+}
+
+impl Default for DeleteTopic {
+    fn default() -> Self {
+        Self {
+            topic: None,
+            require_name: true,
+            form: Form::new(vec![Field::Text(TextField::new(
+                "confirm",
+                "Type the topic ID to confirm",
+            ))]),
+            error: None,
+        }
+    }
+}
+
+impl DeleteTopic {
+    pub fn is_open(&self) -> bool {
+        self.topic.is_some()
+    }
+
+    fn confirmed(&self) -> Result<String, String> {
+        let topic = self.topic.clone().ok_or("No topic selected")?;
+        let typed = self.form.get("confirm").trim();
+        if self.require_name && typed != short_name(&topic) && typed != topic {
+            return Err(format!("Type {} to confirm", short_name(&topic)));
+        }
+        Ok(topic)
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum DeleteTopicEvent {
+    Open(String, bool),
+    Close,
+    Form(FormEvent),
+    Submit,
+}
+
+impl From<DeleteTopicEvent> for AppEvent {
+    fn from(event: DeleteTopicEvent) -> Self {
+        AppEvent::DeleteTopic(event)
+    }
+}
+
+impl From<FormEvent> for DeleteTopicEvent {
+    fn from(event: FormEvent) -> Self {
+        DeleteTopicEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut DeleteTopic, event: DeleteTopicEvent) -> Option<AppEvent> {
+    match event {
+        DeleteTopicEvent::Open(topic, require_name) => {
+            state.topic = Some(topic);
+            state.require_name = require_name;
+            state.error = None;
+            state.form.set("confirm", String::new());
+            match require_name {
+                true => {
+                    Some(DeleteTopicEvent::Form(FormEvent::Focus(Some("confirm".into()))).into())
+                }
+                false => None,
+            }
+        }
+        DeleteTopicEvent::Close => {
+            state.topic = None;
+            state.form.focused = None;
+            None
+        }
+        DeleteTopicEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(DeleteTopicEvent::from)
+            .map(AppEvent::from),
+        DeleteTopicEvent::Submit => match state.confirmed() {
+            Ok(topic) => {
+                state.topic = None;
+                state.form.focused = None;
+                Some(PubsubEvent::DeleteTopic(topic).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &DeleteTopic, key: KeyEvent) -> InputHandled<AppEvent> {
+    if state.require_name {
+        let form_handled = form::on_key(&state.form, key)
+            .map(DeleteTopicEvent::from)
+            .map(AppEvent::from);
+        if form_handled.is_handled() {
+            return form_handled;
+        }
+    }
+    match key.code {
+        Enter => handled(DeleteTopicEvent::Submit.into()),
+        Esc => handled(DeleteTopicEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str = "Enter to delete, Esc to cancel";
+const CONFIRM_HELP: &str = "Spacebar to type the topic ID, Enter to delete, Esc to cancel";
+
+/// `details` is the topic's loaded details, used to list the subscriptions
+/// that will be detached.
+pub fn draw(state: &DeleteTopic, details: Option<&TopicDetails>, f: &mut Frame, area: Rect) {
+    let topic = state.topic.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Delete {topic}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold().fg(Color::LightRed));
+    f.render_widget(block, area);
+
+    let [text_area, fields_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(3)])
+        .margin(1)
+        .areas(area);
+
+    let mut lines = vec![Line::from(match state.require_name {
+        true => CONFIRM_HELP,
+        false => HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        lines.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    lines.push(Line::raw(""));
+    match details {
+        None => lines.push(Line::from("Loading subscriptions...").fg(Color::Gray)),
+        Some(details) if details.subscriptions.is_empty() => {
+            lines.push(Line::raw("No subscriptions are attached to this topic."))
+        }
+        Some(details) => {
+            lines.push(Line::raw(format!(
+                "{} subscriptions will be detached from the topic:",
+                details.subscriptions.len()
+            )));
+            for subscription in &details.subscriptions {
+                lines.push(Line::raw(format!("  {}", short_name(subscription))));
+            }
+        }
+    }
+    let paragraph = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+    f.render_widget(paragraph, text_area);
+
+    if state.require_name {
+        form::draw(&state.form, f, fields_area);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    component::{
+        messages::MessageInfo,
+        pubsub::Pubsub,
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    records::{self, Format},
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::path::PathBuf;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Writes pulled messages to disk in a format that publishing a file reads
+/// back.
+pub struct Export {
+    pub open: bool,
+    pub form: Form,
+    pub error: Option<String>,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        fn choice(label: &str, value: &str) -> Choice {
+            Choice {
+                label: label.into(),
+                value: value.into(),
+            }
+        }
+        Self {
+            open: false,
+            form: Form::new(vec![
+                Field::Text(TextField::new(
+                    "path",
+                    "File, or directory for raw payloads",
+                )),
+                Field::Choices(Choices::new(
+                    "format",
+                    "Format",
+                    vec![
+                        choice("NDJSON", "ndjson"),
+                        choice("CSV", "csv"),
+                        choice("Raw payload files", "raw"),
+                    ],
+                )),
+                Field::Choices(Choices::new(
+                    "scope",
+                    "Messages",
+                    vec![
+                        choice("Everything in the buffer", "buffer"),
+                        choice("Marked or selected", "selected"),
+                    ],
+                )),
+            ]),
+            error: None,
+        }
+    }
+}
+
+impl Export {
+    fn format(&self) -> Format {
+        match self.form.get("format").as_str() {
+            "csv" => Format::Csv,
+            "raw" => Format::Raw,
+            _ => Format::Ndjson,
+        }
+    }
+}
+
+/// Exports the messages chosen in the form, returning how many there were
+/// and the path written, which gets the format's extension if it lacked it.
+fn export(state: &Pubsub) -> Result<(usize, PathBuf), String> {
+    let export = &state.export;
+    let path = export.form.get("path").trim();
+    if path.is_empty() {
+        return Err("Enter where to export to".to_string());
+    }
+    let messages = &state.messages;
+    let chosen: Vec<&MessageInfo> = match export.form.get("scope").as_str() {
+        "selected" if messages.marked.is_empty() => messages.selected().into_iter().collect(),
+        "selected" => messages
+            .all
+            .iter()
+            .filter(|m| messages.marked.contains(&m.message_id))
+            .collect(),
+        _ => messages.all.iter().collect(),
+    };
+    if chosen.is_empty() {
+        return Err("There are no messages to export".to_string());
+    }
+    let format = export.format();
+    let path = format.with_extension(PathBuf::from(path));
+    records::write(&path, format, &chosen)?;
+    Ok((chosen.len(), path))
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum ExportEvent {
+    Open,
+    Close,
+    Form(FormEvent),
+    Submit,
+}
+
+impl From<ExportEvent> for AppEvent {
+    fn from(event: ExportEvent) -> Self {
+        AppEvent::Export(event)
+    }
+}
+
+impl From<FormEvent> for ExportEvent {
+    fn from(event: FormEvent) -> Self {
+        ExportEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: ExportEvent) -> Option<AppEvent> {
+    match event {
+        ExportEvent::Open => {
+            let export = &mut state.export;
+            export.open = true;
+            export.error = None;
+            if export.form.get("format").is_empty() {
+                export.form.set("format", "ndjson".to_string());
+            }
+            let scope = match state.messages.marked.is_empty() {
+                true => "buffer",
+                false => "selected",
+            };
+            export.form.set("scope", scope.to_string());
+            Some(ExportEvent::Form(FormEvent::Focus(Some("path".into()))).into())
+        }
+        ExportEvent::Close => {
+            state.export.open = false;
+            state.export.form.focused = None;
+            None
+        }
+        ExportEvent::Form(e) => form::on_event(&mut state.export.form, e)
+            .map(ExportEvent::from)
+            .map(AppEvent::from),
+        ExportEvent::Submit => {
+            match export(state) {
+                Ok((count, path)) => {
+                    state.export.open = false;
+                    state.export.form.focused = None;
+                    state.status.info =
+                        Some(format!("Exported {count} messages to {}", path.display()));
+                }
+                Err(e) => state.export.error = Some(e),
+            }
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Export, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(ExportEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter => handled(ExportEvent::Submit.into()),
+        Esc => handled(ExportEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to export, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &Export, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .title("Export messages")
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let mut help = vec![Line::from(match state.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+}
@@ -0,0 +1,555 @@
+use crate::{
+    component::{
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::{send_event, AppEvent},
+    input::{handled, handled_empty, InputHandled},
+    latency::{format_latency, Latencies},
+    util::{format_duration, parse_duration, parse_key_values, parse_rate, short_name},
+};
+use google_cloud_gax::grpc::Status;
+use google_cloud_googleapis::pubsub::v1::PubsubMessage;
+use google_cloud_pubsub::{
+    publisher::{Publisher, PublisherConfig},
+    topic::Topic,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Sparkline, Wrap},
+    Frame,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc, Semaphore},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Publishes synthetic messages to `topic` to see how fast it, or the
+/// emulator, accepts them.
+#[derive(Debug, Clone)]
+pub struct LoadTestJob {
+    pub topic: String,
+    /// Time between messages, `None` for as fast as possible.
+    pub period: Option<Duration>,
+    /// How long to publish for, `None` until stopped.
+    pub duration: Option<Duration>,
+    pub payload_size: usize,
+    pub attributes: HashMap<String, String>,
+    /// How many ordering keys the messages are spread over, 0 for none.
+    pub ordering_keys: usize,
+    /// How many messages may be waiting for the server at once.
+    pub in_flight: usize,
+    pub publisher: PublisherConfig,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LoadTestProgress {
+    pub published: u64,
+    pub failed: u64,
+    /// Failures by gRPC status code.
+    pub errors: BTreeMap<String, u64>,
+    pub last_error: Option<String>,
+    /// From handing a message to the publisher until the server acknowledged
+    /// it, so batching delays are included.
+    pub latencies: Latencies,
+    pub done: bool,
+}
+
+/// The load test dialog, and the run started from it which keeps going in
+/// a background task when the dialog is closed.
+pub struct LoadTest {
+    pub topic: Option<String>,
+    pub form: Form,
+    pub error: Option<String>,
+    pub job: Option<LoadTestJob>,
+    pub progress: LoadTestProgress,
+    /// Messages per second over each report interval, newest last.
+    pub throughput: Vec<u64>,
+    reported: (Instant, u64),
+    started: Instant,
+    finished: Option<Duration>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Default for LoadTest {
+    fn default() -> Self {
+        Self {
+            topic: None,
+            form: Form::new(vec![
+                Field::Text(TextField::new(
+                    "rate",
+                    "Messages per second (0 for as fast as possible)",
+                )),
+                Field::Text(TextField::new(
+                    "duration",
+                    "Run for (e.g. 30s, 5m, empty until stopped)",
+                )),
+                Field::Text(TextField::new("payload_size", "Payload size in bytes")),
+                Field::Text(TextField::new(
+                    "attributes",
+                    "Attributes (key=value, comma separated)",
+                )),
+                Field::Text(TextField::new(
+                    "ordering_keys",
+                    "Ordering keys to spread messages over (0 for none)",
+                )),
+                Field::Text(TextField::new("in_flight", "Messages in flight at once")),
+                Field::Text(TextField::new("workers", "Publisher workers")),
+                Field::Text(TextField::new("bundle_size", "Messages per batch")),
+                Field::Text(TextField::new(
+                    "flush_interval",
+                    "Batch flush interval in milliseconds",
+                )),
+            ]),
+            error: None,
+            job: None,
+            progress: LoadTestProgress::default(),
+            throughput: Vec::new(),
+            reported: (Instant::now(), 0),
+            started: Instant::now(),
+            finished: None,
+            task: None,
+        }
+    }
+}
+
+const DEFAULT_FIELD_VALUES: &[(&str, &str)] = &[
+    ("rate", "1000"),
+    ("payload_size", "1024"),
+    ("ordering_keys", "0"),
+    ("in_flight", "1000"),
+    ("workers", "3"),
+    ("bundle_size", "100"),
+    ("flush_interval", "10"),
+];
+/// Pub/Sub rejects messages over 10 MB.
+const MAX_PAYLOAD_SIZE: usize = 10_000_000;
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+/// Throughput samples kept for the sparkline.
+const MAX_THROUGHPUT_SAMPLES: usize = 240;
+const PERCENTILES: [f64; 4] = [50.0, 90.0, 99.0, 99.9];
+
+impl LoadTest {
+    pub fn is_open(&self) -> bool {
+        self.topic.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    fn job(&self) -> Result<LoadTestJob, String> {
+        let topic = self.topic.clone().ok_or("No topic selected")?;
+        fn number<T: std::str::FromStr>(form: &Form, name: &str, what: &str) -> Result<T, String> {
+            form.get(name)
+                .trim()
+                .parse()
+                .map_err(|_| format!("The {what} must be a whole number"))
+        }
+        let period = parse_rate(self.form.get("rate"))?;
+        let duration = match self.form.get("duration").trim() {
+            "" => None,
+            duration => Some(parse_duration(duration)?).filter(|d| !d.is_zero()),
+        };
+        let in_flight: usize = number(&self.form, "in_flight", "messages in flight")?;
+        let workers: usize = number(&self.form, "workers", "number of workers")?;
+        let bundle_size: usize = number(&self.form, "bundle_size", "batch size")?;
+        if in_flight == 0 || workers == 0 || bundle_size == 0 {
+            return Err("Messages in flight, workers and batch size must be positive".to_string());
+        }
+        if in_flight > Semaphore::MAX_PERMITS {
+            return Err("Too many messages in flight".to_string());
+        }
+        let payload_size: usize = number(&self.form, "payload_size", "payload size")?;
+        if payload_size > MAX_PAYLOAD_SIZE {
+            return Err(format!(
+                "The payload size can be at most {MAX_PAYLOAD_SIZE} bytes"
+            ));
+        }
+        Ok(LoadTestJob {
+            topic,
+            period,
+            duration,
+            payload_size,
+            attributes: parse_key_values(self.form.get("attributes"))?,
+            ordering_keys: number(&self.form, "ordering_keys", "number of ordering keys")?,
+            in_flight,
+            publisher: PublisherConfig {
+                workers,
+                bundle_size,
+                flush_interval: Duration::from_millis(number(
+                    &self.form,
+                    "flush_interval",
+                    "flush interval",
+                )?),
+                ..Default::default()
+            },
+        })
+    }
+
+    pub fn start(&mut self, topic: Topic, job: LoadTestJob) {
+        self.stop();
+        self.progress = LoadTestProgress::default();
+        self.throughput.clear();
+        self.started = Instant::now();
+        self.reported = (self.started, 0);
+        self.finished = None;
+        self.task = Some(spawn_load_test(topic, job.clone()));
+        self.job = Some(job);
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(handle) = self.task.take() {
+            handle.abort();
+            self.finished = Some(self.started.elapsed());
+        }
+    }
+
+    fn add_progress(&mut self, progress: LoadTestProgress) {
+        let now = Instant::now();
+        let (at, published) = self.reported;
+        let seconds = now.duration_since(at).as_secs_f64().max(0.001);
+        let rate = (progress.published.saturating_sub(published) as f64 / seconds) as u64;
+        self.throughput.push(rate);
+        if self.throughput.len() > MAX_THROUGHPUT_SAMPLES {
+            self.throughput.remove(0);
+        }
+        self.reported = (now, progress.published);
+        self.progress = progress;
+    }
+}
+
+/// How long the message took to be acknowledged, and how the server
+/// answered.
+type PublishResult = (Duration, Result<(), Status>);
+
+fn spawn_load_test(topic: Topic, job: LoadTestJob) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut publisher = topic.new_publisher(Some(job.publisher.clone()));
+        let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+        let feeder = feed(publisher.clone(), job, result_tx);
+
+        let mut progress = LoadTestProgress::default();
+        // Reports go out on a timer, so throughput drops show up even when
+        // nothing is being acknowledged.
+        let collector = async {
+            let mut ticker = interval(REPORT_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    result = result_rx.recv() => {
+                        let Some((latency, result)) = result else {
+                            break;
+                        };
+                        match result {
+                            Ok(()) => {
+                                progress.published += 1;
+                                progress.latencies.record(latency);
+                            }
+                            Err(e) => {
+                                progress.failed += 1;
+                                *progress.errors.entry(format!("{:?}", e.code())).or_default() += 1;
+                                progress.last_error = Some(e.message().to_string());
+                            }
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        send_event(LoadTestEvent::Progress(progress.clone()).into()).await;
+                    }
+                }
+            }
+        };
+        tokio::join!(feeder, collector);
+        publisher.shutdown().await;
+        progress.done = true;
+        send_event(LoadTestEvent::Progress(progress).into()).await;
+    })
+}
+
+/// Payloads start with the message's sequence number and are padded to the
+/// requested size.
+fn payload(sequence: u64, size: usize) -> Vec<u8> {
+    let mut data = format!("{sequence} ").into_bytes();
+    data.resize(size, b'.');
+    data
+}
+
+async fn feed(
+    publisher: Publisher,
+    job: LoadTestJob,
+    results: mpsc::UnboundedSender<PublishResult>,
+) {
+    let permits = Arc::new(Semaphore::new(job.in_flight));
+    let mut ticker = job.period.map(|period| {
+        let mut ticker = interval(period);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+        ticker
+    });
+    let started = Instant::now();
+    for sequence in 0.. {
+        if job.duration.is_some_and(|d| started.elapsed() >= d) {
+            return;
+        }
+        if let Some(ticker) = ticker.as_mut() {
+            ticker.tick().await;
+        }
+        let Ok(permit) = permits.clone().acquire_owned().await else {
+            return;
+        };
+        let ordering_key = match job.ordering_keys {
+            0 => String::new(),
+            keys => format!("key-{}", sequence % keys as u64),
+        };
+        let sent = Instant::now();
+        let awaiter = publisher
+            .publish(PubsubMessage {
+                data: payload(sequence, job.payload_size),
+                attributes: job.attributes.clone(),
+                ordering_key,
+                ..Default::default()
+            })
+            .await;
+        let results = results.clone();
+        tokio::spawn(async move {
+            let result = awaiter.get().await.map(|_| ());
+            drop(permit);
+            let _ = results.send((sent.elapsed(), result));
+        });
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum LoadTestEvent {
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Start,
+    Progress(LoadTestProgress),
+    Stop,
+}
+
+impl From<LoadTestEvent> for AppEvent {
+    fn from(event: LoadTestEvent) -> Self {
+        AppEvent::LoadTest(event)
+    }
+}
+
+impl From<FormEvent> for LoadTestEvent {
+    fn from(event: FormEvent) -> Self {
+        LoadTestEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: LoadTestEvent) -> Option<AppEvent> {
+    let load = &mut state.load_test;
+    match event {
+        LoadTestEvent::Open(topic) => {
+            load.topic = Some(topic);
+            load.error = None;
+            for (name, value) in DEFAULT_FIELD_VALUES {
+                if load.form.get(name).is_empty() {
+                    load.form.set(name, value.to_string());
+                }
+            }
+            Some(LoadTestEvent::Form(FormEvent::Focus(Some("rate".into()))).into())
+        }
+        LoadTestEvent::Close => {
+            load.topic = None;
+            load.form.focused = None;
+            None
+        }
+        LoadTestEvent::Form(e) => form::on_event(&mut load.form, e)
+            .map(LoadTestEvent::from)
+            .map(AppEvent::from),
+        LoadTestEvent::Start => match load.job() {
+            Ok(job) => {
+                load.error = None;
+                load.form.focused = None;
+                Some(PubsubEvent::StartLoadTest(Box::new(job)).into())
+            }
+            Err(e) => {
+                load.error = Some(e);
+                None
+            }
+        },
+        LoadTestEvent::Progress(progress) => {
+            // Reports still queued from a stopped run are dropped.
+            if !load.is_running() {
+                return None;
+            }
+            let done = progress.done;
+            load.add_progress(progress);
+            if done {
+                load.task = None;
+                load.finished = Some(load.started.elapsed());
+                state.status.info = Some(format!(
+                    "Load test published {} messages, {} failed",
+                    load.progress.published, load.progress.failed
+                ));
+            }
+            None
+        }
+        LoadTestEvent::Stop => {
+            load.stop();
+            state.status.info = Some("Load test stopped".to_string());
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &LoadTest, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(LoadTestEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter if !state.is_running() => handled(LoadTestEvent::Start.into()),
+        Char('x') if state.is_running() => handled(LoadTestEvent::Stop.into()),
+        Esc => handled(LoadTestEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to start, Esc to close";
+const RUNNING_HELP: &str = "The test continues when closed. x to stop, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &LoadTest, f: &mut Frame, area: Rect) {
+    let topic = state.topic.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Load test {topic}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area, stats_area, throughput_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Min(6),
+        Constraint::Length(8),
+        Constraint::Length(6),
+    ])
+    .margin(1)
+    .areas(area);
+    let mut help = vec![
+        Line::from(match (state.form.is_editing(), state.is_running()) {
+            (true, _) => EDITING_HELP,
+            (false, true) => RUNNING_HELP,
+            (false, false) => VIEWING_HELP,
+        })
+        .fg(Color::Gray),
+    ];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+
+    if state.job.is_none() {
+        return;
+    }
+    let progress = &state.progress;
+    let elapsed = state.finished.unwrap_or(state.started.elapsed());
+    let rate = progress.published as f64 / elapsed.as_secs_f64().max(0.001);
+    let status = match (state.is_running(), progress.done) {
+        (true, _) => "running",
+        (false, true) => "done",
+        (false, false) => "stopped",
+    };
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    let percentiles: Vec<String> = PERCENTILES
+        .iter()
+        .map(|p| {
+            let latency = progress.latencies.percentile(*p);
+            format!("p{p} {}", latency.map_or("-".to_string(), format_latency))
+        })
+        .collect();
+    let errors: Vec<String> = progress
+        .errors
+        .iter()
+        .map(|(code, count)| format!("{code} {count}"))
+        .collect();
+    let mut lines = vec![
+        field(
+            "Status",
+            format!("{status} for {}", format_duration(elapsed)),
+        ),
+        field(
+            "Published",
+            format!(
+                "{} ({rate:.0} msg/s, {:.2} MB/s)",
+                progress.published,
+                rate * state.job.as_ref().map_or(0, |j| j.payload_size) as f64 / 1_000_000.0
+            ),
+        ),
+        field("Latency", percentiles.join(", ")),
+        field("Max latency", format_latency(progress.latencies.max())),
+        field(
+            "Failed",
+            match errors.is_empty() {
+                true => progress.failed.to_string(),
+                false => format!("{} ({})", progress.failed, errors.join(", ")),
+            },
+        ),
+    ];
+    if let Some(error) = &progress.last_error {
+        lines.push(Line::from(format!("Last error: {error}")).fg(Color::LightRed));
+    }
+    let stats = Paragraph::new(Text::from(lines))
+        .block(Block::default().title("Results").borders(Borders::ALL))
+        .wrap(Wrap { trim: false });
+    f.render_widget(stats, stats_area);
+
+    let current = state.throughput.last().copied().unwrap_or_default();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("Throughput ({current} msg/s)"))
+                .borders(Borders::ALL),
+        )
+        .style(Style::default().fg(Color::LightGreen))
+        .data(
+            &state.throughput[state
+                .throughput
+                .len()
+                .saturating_sub(throughput_area.width as usize)..],
+        );
+    f.render_widget(sparkline, throughput_area);
+}
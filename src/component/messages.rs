@@ -0,0 +1,716 @@
+use crate::{
+    app::App,
+    codec::Decoded,
+    component::{
+        decoders::{self, DecodersEvent},
+        export::{self, ExportEvent},
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            hex_view::{self, HexView, HexViewEvent},
+            json_view::{self, JsonView, JsonViewEvent},
+        },
+        tail::{self, TailEvent},
+    },
+    event::AppEvent,
+    input::{handled, not_handled, InputHandled},
+    util::{format_timestamp, short_name},
+};
+use google_cloud_pubsub::subscriber::ReceivedMessage;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Up},
+        KeyEvent,
+    },
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    time::SystemTime,
+};
+use strum_macros::Display;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum AckState {
+    #[strum(serialize = "Leased")]
+    Leased,
+    #[strum(serialize = "Acked")]
+    Acked,
+    #[strum(serialize = "Nacked")]
+    Nacked,
+}
+
+#[derive(Debug, Clone)]
+pub struct MessageInfo {
+    pub ack_id: String,
+    pub message_id: String,
+    pub publish_time: Option<SystemTime>,
+    pub data: Vec<u8>,
+    pub attributes: BTreeMap<String, String>,
+    pub ordering_key: String,
+    pub delivery_attempt: Option<usize>,
+    pub state: AckState,
+    /// Set when a decoder is registered for the message's topic.
+    pub decoded: Option<Decoded>,
+}
+
+impl From<&ReceivedMessage> for MessageInfo {
+    fn from(received: &ReceivedMessage) -> Self {
+        let message = &received.message;
+        Self {
+            ack_id: received.ack_id().to_string(),
+            message_id: message.message_id.clone(),
+            publish_time: message
+                .publish_time
+                .clone()
+                .and_then(|t| SystemTime::try_from(t).ok()),
+            data: message.data.clone(),
+            attributes: message.attributes.clone().into_iter().collect(),
+            ordering_key: message.ordering_key.clone(),
+            delivery_attempt: received.delivery_attempt(),
+            state: AckState::Leased,
+            decoded: None,
+        }
+    }
+}
+
+impl MessageInfo {
+    /// A single-line, length-limited rendering of the payload for list views.
+    pub fn preview(&self, max_chars: usize) -> String {
+        let text = match &self.decoded {
+            Some(Decoded {
+                result: Ok(text), ..
+            }) => text.split_whitespace().collect::<Vec<_>>().join(" "),
+            // Payloads that failed to decode are previewed as hex bytes.
+            Some(Decoded { result: Err(_), .. }) => self
+                .data
+                .iter()
+                .take(max_chars / 3)
+                .map(|b| format!("{b:02x} "))
+                .collect(),
+            None => String::from_utf8_lossy(&self.data).to_string(),
+        };
+        text.chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .take(max_chars)
+            .collect()
+    }
+}
+
+/// How the list arranges messages by ordering key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum KeyView {
+    #[default]
+    All,
+    /// Messages with the same ordering key are listed together, in the order
+    /// they were received.
+    Grouped,
+    /// Only messages with this ordering key are listed.
+    Only(String),
+}
+
+/// Numeric attributes publishers commonly use to number the messages of an
+/// ordering key. Pub/Sub has no such field, so this is a guess: the first
+/// name found on a keyed message is used for the whole buffer, and the list
+/// title says which one.
+const SEQUENCE_ATTRIBUTES: [&str; 4] = ["seq", "sequence", "sequence_number", "sequenceNumber"];
+
+fn sequence_number(message: &MessageInfo, attribute: Option<&str>) -> Option<u64> {
+    attribute
+        .and_then(|name| message.attributes.get(name))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+pub const DEFAULT_BATCH_SIZE: i32 = 10;
+const MAX_BATCH_SIZE: i32 = 1000;
+/// Oldest messages are dropped past this so a long tail can't grow unbounded.
+const MAX_BUFFERED: usize = 10_000;
+
+/// Messages pulled from a single subscription. The buffer is cleared when
+/// pulling from a different subscription since ack IDs are only valid on
+/// the subscription they were received from.
+pub struct Messages {
+    pub subscription: Option<String>,
+    pub all: Vec<MessageInfo>,
+    pub selected: Option<usize>,
+    pub batch_size: i32,
+    /// The selected message's payload.
+    pub payload: JsonView,
+    pub hex: HexView,
+    pub show_hex: bool,
+    pub key_view: KeyView,
+    /// IDs of the messages marked for export.
+    pub marked: HashSet<String>,
+    /// Derived from `all` and `key_view` by `refresh`, so drawing and key
+    /// handling don't sort and scan the buffer every frame.
+    visible: Vec<usize>,
+    gaps: HashMap<usize, String>,
+    sequence_attribute: Option<&'static str>,
+}
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            all: Vec::new(),
+            selected: None,
+            batch_size: DEFAULT_BATCH_SIZE,
+            payload: JsonView::default(),
+            hex: HexView::default(),
+            show_hex: false,
+            key_view: KeyView::All,
+            marked: HashSet::new(),
+            visible: Vec::new(),
+            gaps: HashMap::new(),
+            sequence_attribute: None,
+        }
+    }
+}
+
+impl Messages {
+    /// Returns the messages still leased from the previous subscription when
+    /// this one is different, and those dropped to stay within
+    /// `MAX_BUFFERED`, for the caller to nack so they are redelivered right
+    /// away rather than when their lease runs out.
+    pub fn add_messages(
+        &mut self,
+        subscription: String,
+        messages: Vec<MessageInfo>,
+    ) -> Vec<(String, Vec<String>)> {
+        let mut released = Vec::new();
+        if self.subscription.as_ref() != Some(&subscription) {
+            released.extend(self.release());
+            self.clear();
+            self.subscription = Some(subscription.clone());
+        }
+        self.all.extend(messages);
+        let overflow = self.all.len().saturating_sub(MAX_BUFFERED);
+        if overflow > 0 {
+            let mut dropped = Vec::new();
+            for m in self.all.drain(..overflow) {
+                self.marked.remove(&m.message_id);
+                if m.state == AckState::Leased {
+                    dropped.push(m.ack_id);
+                }
+            }
+            if !dropped.is_empty() {
+                released.push((subscription, dropped));
+            }
+            self.selected = self.selected.map(|i| i.saturating_sub(overflow));
+        }
+        if self.selected.is_none() && !self.all.is_empty() {
+            self.selected = Some(0);
+        }
+        self.refresh();
+        self.sync_payload();
+        released
+    }
+
+    /// Empties the buffer without touching the messages' leases.
+    pub fn clear(&mut self) {
+        self.all.clear();
+        self.marked.clear();
+        self.selected = None;
+        self.refresh();
+    }
+
+    /// The subscription and ack IDs of the messages still leased.
+    fn release(&self) -> Option<(String, Vec<String>)> {
+        let ack_ids = self.leased_ack_ids();
+        match (&self.subscription, ack_ids.is_empty()) {
+            (Some(subscription), false) => Some((subscription.clone(), ack_ids)),
+            _ => None,
+        }
+    }
+
+    /// Recomputes the list order and sequence gaps. Call whenever `all` or
+    /// `key_view` changes.
+    fn refresh(&mut self) {
+        self.sequence_attribute = SEQUENCE_ATTRIBUTES.into_iter().find(|name| {
+            self.all
+                .iter()
+                .any(|m| !m.ordering_key.is_empty() && m.attributes.contains_key(*name))
+        });
+        self.visible = match &self.key_view {
+            KeyView::Only(key) => (0..self.all.len())
+                .filter(|&i| &self.all[i].ordering_key == key)
+                .collect(),
+            _ => (0..self.all.len()).collect(),
+        };
+        if self.key_view == KeyView::Grouped {
+            let all = &self.all;
+            self.visible
+                .sort_by(|&a, &b| all[a].ordering_key.cmp(&all[b].ordering_key));
+        }
+        self.gaps = match self.key_view {
+            KeyView::All => HashMap::new(),
+            _ => self.find_sequence_gaps(),
+        };
+    }
+
+    /// Shows the selected message's payload, decoded if possible. A newly
+    /// selected payload starts in the hex view if it isn't text.
+    pub fn sync_payload(&mut self) {
+        let selected = self.selected.and_then(|i| self.all.get(i));
+        let data = selected.map(|m| m.data.as_slice()).unwrap_or_default();
+        if self.hex.set_data(data) {
+            self.show_hex = selected.is_some_and(|m| match &m.decoded {
+                Some(decoded) => decoded.result.is_err(),
+                None => std::str::from_utf8(&m.data).is_err(),
+            });
+        }
+        let text = match selected {
+            Some(MessageInfo {
+                decoded: Some(Decoded {
+                    result: Ok(text), ..
+                }),
+                ..
+            }) => text.clone(),
+            Some(message) if message.decoded.is_none() => {
+                String::from_utf8_lossy(&message.data).to_string()
+            }
+            _ => String::new(),
+        };
+        self.payload.set_text(&text);
+    }
+
+    pub fn set_state(&mut self, ack_ids: &[String], state: AckState) {
+        let ack_ids: HashSet<&str> = ack_ids.iter().map(String::as_str).collect();
+        for message in self.all.iter_mut() {
+            if ack_ids.contains(message.ack_id.as_str()) {
+                message.state = state;
+            }
+        }
+    }
+
+    pub fn selected(&self) -> Option<&MessageInfo> {
+        self.selected.and_then(|i| self.all.get(i))
+    }
+
+    /// Indices into `all` in the order the list shows them.
+    pub fn visible(&self) -> &[usize] {
+        &self.visible
+    }
+
+    /// Messages that don't follow the previous message received with the
+    /// same ordering key, keyed by index into `all`. Only computed while
+    /// the list is grouped or filtered by ordering key.
+    pub fn sequence_gaps(&self) -> &HashMap<usize, String> {
+        &self.gaps
+    }
+
+    /// Messages whose sequence attribute skips or goes back, or that were
+    /// published before the previous message with the same ordering key.
+    fn find_sequence_gaps(&self) -> HashMap<usize, String> {
+        let attribute = self.sequence_attribute;
+        let mut gaps = HashMap::new();
+        let mut previous: HashMap<&str, &MessageInfo> = HashMap::new();
+        for (i, message) in self.all.iter().enumerate() {
+            if message.ordering_key.is_empty() {
+                continue;
+            }
+            if let Some(last) = previous.insert(&message.ordering_key, message) {
+                let gap = match (
+                    sequence_number(last, attribute),
+                    sequence_number(message, attribute),
+                ) {
+                    (Some(a), Some(b)) if b == a => Some(format!("repeats {b}")),
+                    (Some(a), Some(b)) if b < a => Some(format!("{a} → {b}, out of order")),
+                    (Some(a), Some(b)) if b > a + 1 => {
+                        Some(format!("{a} → {b}, {} missing", b - a - 1))
+                    }
+                    (Some(_), Some(_)) => None,
+                    _ if message.publish_time.is_some()
+                        && message.publish_time < last.publish_time =>
+                    {
+                        Some("published before the previous message".to_string())
+                    }
+                    _ => None,
+                };
+                if let Some(gap) = gap {
+                    gaps.insert(i, gap);
+                }
+            }
+        }
+        gaps
+    }
+
+    fn leased_ack_ids(&self) -> Vec<String> {
+        self.all
+            .iter()
+            .filter(|m| m.state == AckState::Leased)
+            .map(|m| m.ack_id.clone())
+            .collect()
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum MessagesEvent {
+    Select(Option<usize>),
+    SetBatchSize(i32),
+    SetState(Vec<String>, AckState),
+    Clear,
+    Payload(JsonViewEvent),
+    Hex(HexViewEvent),
+    ToggleHex,
+    SetKeyView(KeyView),
+    ToggleMark(String),
+}
+
+impl From<MessagesEvent> for AppEvent {
+    fn from(event: MessagesEvent) -> Self {
+        AppEvent::Messages(event)
+    }
+}
+
+pub fn on_event(state: &mut Messages, event: MessagesEvent) -> Option<AppEvent> {
+    let chained = match event {
+        MessagesEvent::Hex(e) => hex_view::on_event(&mut state.hex, e)
+            .map(MessagesEvent::Hex)
+            .map(AppEvent::from),
+        // Leased messages are nacked so they don't wait out their lease.
+        MessagesEvent::Clear => {
+            let released = state.release();
+            state.clear();
+            released.map(|(subscription, ack_ids)| PubsubEvent::Nack(subscription, ack_ids).into())
+        }
+        event => {
+            on_message_event(state, event);
+            None
+        }
+    };
+    state.sync_payload();
+    chained
+}
+
+fn on_message_event(state: &mut Messages, event: MessagesEvent) {
+    match event {
+        MessagesEvent::Select(idx) => state.selected = idx,
+        MessagesEvent::SetBatchSize(n) => state.batch_size = n.clamp(1, MAX_BATCH_SIZE),
+        MessagesEvent::SetState(ack_ids, ack_state) => state.set_state(&ack_ids, ack_state),
+        MessagesEvent::Payload(e) => {
+            json_view::on_event(&mut state.payload, e);
+        }
+        MessagesEvent::ToggleHex => state.show_hex = !state.show_hex,
+        MessagesEvent::ToggleMark(id) => {
+            if !state.marked.remove(&id) {
+                state.marked.insert(id);
+            }
+        }
+        MessagesEvent::SetKeyView(view) => {
+            state.key_view = view;
+            state.refresh();
+            if !state.selected.is_some_and(|i| state.visible.contains(&i)) {
+                state.selected = state.visible.first().copied();
+            }
+        }
+        MessagesEvent::Hex(_) | MessagesEvent::Clear => {}
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+/// New batches and tails are taken from the subscription currently selected
+/// on the Subscriptions route.
+pub fn on_key(pubsub: &Pubsub, key: KeyEvent) -> InputHandled<AppEvent> {
+    let state = &pubsub.messages;
+    let payload_handled = match state.show_hex {
+        true => hex_view::on_key(&state.hex, key).map(MessagesEvent::Hex),
+        false => json_view::on_key(&state.payload, key).map(MessagesEvent::Payload),
+    };
+    if payload_handled.is_handled() {
+        return payload_handled.map(AppEvent::from);
+    }
+    let subscription = pubsub.subscriptions.selected().map(|s| s.name.as_str());
+    let visible = state.visible();
+    let count = visible.len();
+    let position = state
+        .selected
+        .and_then(|i| visible.iter().position(|&v| v == i));
+    let select = |position: usize| handled(MessagesEvent::Select(Some(visible[position])).into());
+    let selected_leased = state
+        .selected()
+        .filter(|m| m.state == AckState::Leased)
+        .map(|m| {
+            (
+                state.subscription.clone().unwrap_or_default(),
+                vec![m.ack_id.clone()],
+            )
+        });
+    match key.code {
+        Up | Down if count == 0 => not_handled(),
+        Up => select(position.map_or(count - 1, |i| (i + count - 1) % count)),
+        Down => select(position.map_or(0, |i| (i + 1) % count)),
+        Char('p') => match subscription {
+            Some(subscription) => {
+                handled(PubsubEvent::Pull(subscription.to_string(), state.batch_size).into())
+            }
+            None => not_handled(),
+        },
+        Char('+') => handled(MessagesEvent::SetBatchSize(state.batch_size * 2).into()),
+        Char('-') => handled(MessagesEvent::SetBatchSize(state.batch_size / 2).into()),
+        Char('a') => match selected_leased {
+            Some((subscription, ack_ids)) => {
+                handled(PubsubEvent::Ack(subscription, ack_ids).into())
+            }
+            None => not_handled(),
+        },
+        Char('n') => match selected_leased {
+            Some((subscription, ack_ids)) => {
+                handled(PubsubEvent::Nack(subscription, ack_ids).into())
+            }
+            None => not_handled(),
+        },
+        Char('l') => match selected_leased {
+            Some((subscription, ack_ids)) => {
+                handled(PubsubEvent::ExtendLease(subscription, ack_ids).into())
+            }
+            None => not_handled(),
+        },
+        Char('A') => match (&state.subscription, state.leased_ack_ids()) {
+            (Some(subscription), ack_ids) if !ack_ids.is_empty() => {
+                handled(PubsubEvent::Ack(subscription.clone(), ack_ids).into())
+            }
+            _ => not_handled(),
+        },
+        Char('c') => handled(MessagesEvent::Clear.into()),
+        Char('f') if pubsub.tail.is_running() => handled(TailEvent::Stop.into()),
+        Char('f') => match subscription {
+            Some(subscription) => handled(PubsubEvent::StartTail(subscription.to_string()).into()),
+            None => not_handled(),
+        },
+        Char(' ') if pubsub.tail.is_running() => handled(TailEvent::TogglePause.into()),
+        Char('x') => handled(TailEvent::ToggleAutoAck.into()),
+        Char('o') => handled(
+            MessagesEvent::SetKeyView(match (&state.key_view, state.selected()) {
+                (KeyView::All, _) => KeyView::Grouped,
+                (KeyView::Grouped, Some(message)) => KeyView::Only(message.ordering_key.clone()),
+                _ => KeyView::All,
+            })
+            .into(),
+        ),
+        Char('m') => match state.selected() {
+            Some(message) => handled(MessagesEvent::ToggleMark(message.message_id.clone()).into()),
+            None => not_handled(),
+        },
+        Char('e') if !state.all.is_empty() => handled(ExportEvent::Open.into()),
+        Char('v') if state.selected.is_some() => handled(MessagesEvent::ToggleHex.into()),
+        Char('D') => {
+            let topic = subscription
+                .and_then(|name| pubsub.subscriptions.all.iter().find(|s| s.name == name))
+                .map(|s| short_name(&s.topic).to_string())
+                .unwrap_or_default();
+            handled(DecodersEvent::Open(topic).into())
+        }
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str = "↑/↓ select, p pull, +/- batch size, a ack, n nack, l keep leased, A ack all, \
+     c clear, f follow, Space pause, x auto-ack, v hex view, o ordering keys, m mark, e export, D decoders";
+
+pub fn draw(state: &App, f: &mut Frame, area: Rect) {
+    let messages = &state.pubsub.messages;
+    let pull_from = state
+        .pubsub
+        .subscriptions
+        .selected()
+        .map(|s| short_name(&s.name).to_string())
+        .unwrap_or("no subscription selected".to_string());
+    let mut title = format!(
+        "Messages from {} (batch {})",
+        pull_from, messages.batch_size
+    );
+    match &messages.key_view {
+        KeyView::All => {}
+        KeyView::Grouped => title.push_str(" grouped by ordering key"),
+        KeyView::Only(key) if key.is_empty() => title.push_str(" without an ordering key"),
+        KeyView::Only(key) => title = format!("{title} with ordering key '{key}'"),
+    }
+    if messages.key_view != KeyView::All {
+        title.push_str(&match messages.sequence_attribute {
+            Some(name) => format!(", sequence guessed from attribute '{name}'"),
+            None => format!(
+                ", no sequence attribute ({}) found",
+                SEQUENCE_ATTRIBUTES.join(", ")
+            ),
+        });
+    }
+    if let Some(summary) = tail::summary(&state.pubsub.tail) {
+        title = format!("{title} {summary}");
+    }
+    let block = Block::default()
+        .title(title)
+        .title_bottom(HELP)
+        .fg(Color::LightYellow)
+        .bg(Color::Black)
+        .borders(Borders::ALL);
+    f.render_widget(block, area);
+
+    let [list_area, details_area] = Layout::default()
+        .margin(1)
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .areas(area);
+    draw_list(messages, f, list_area);
+    if state.pubsub.decoders.open {
+        decoders::draw(&state.pubsub.decoders, f, details_area);
+    } else if state.pubsub.export.open {
+        export::draw(&state.pubsub.export, f, details_area);
+    } else {
+        draw_details(messages, f, details_area);
+    }
+}
+
+fn draw_list(messages: &Messages, f: &mut Frame, area: Rect) {
+    let by_key = messages.key_view != KeyView::All;
+    let gaps = messages.sequence_gaps();
+    let header = Row::new(match by_key {
+        true => vec!["Published", "Ordering key", "State", "Sequence", "Payload"],
+        false => vec!["Published", "Message ID", "State", "Payload"],
+    })
+    .style(Style::default().bold().fg(Color::LightCyan));
+    let visible = messages.visible();
+    let rows = visible.iter().map(|&i| {
+        let m = &messages.all[i];
+        let mut style = match m.state {
+            AckState::Leased => Style::default(),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        let published = m.publish_time.map(format_timestamp).unwrap_or_default();
+        let mut cells = vec![match messages.marked.contains(&m.message_id) {
+            true => format!("● {published}"),
+            false => published,
+        }];
+        if by_key {
+            cells.push(m.ordering_key.clone());
+            cells.push(m.state.to_string());
+            let sequence = sequence_number(m, messages.sequence_attribute).map(|n| n.to_string());
+            cells.push(match gaps.get(&i) {
+                Some(gap) => {
+                    style = style.fg(Color::LightRed);
+                    gap.clone()
+                }
+                None => sequence.unwrap_or_default(),
+            });
+        } else {
+            cells.push(m.message_id.clone());
+            cells.push(m.state.to_string());
+        }
+        cells.push(m.preview(80));
+        Row::new(cells).style(style)
+    });
+    let widths = match by_key {
+        true => vec![
+            Constraint::Length(24),
+            Constraint::Length(16),
+            Constraint::Length(7),
+            Constraint::Length(24),
+            Constraint::Min(0),
+        ],
+        false => vec![
+            Constraint::Length(24),
+            Constraint::Length(18),
+            Constraint::Length(7),
+            Constraint::Min(0),
+        ],
+    };
+    let table = Table::new(rows, widths)
+        .header(header)
+        .row_highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+        .block(Block::default().borders(Borders::ALL));
+    let selected = messages
+        .selected
+        .and_then(|i| visible.iter().position(|&v| v == i));
+    let mut table_state = TableState::default().with_selected(selected);
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn draw_details(messages: &Messages, f: &mut Frame, area: Rect) {
+    let block = Block::default().title("Message").borders(Borders::ALL);
+    let Some(message) = messages.selected() else {
+        let empty = Paragraph::new("Press p to pull messages").fg(Color::Gray);
+        f.render_widget(empty.block(block), area);
+        return;
+    };
+
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    let mut lines = vec![
+        field("Message ID", message.message_id.clone()),
+        field(
+            "Published",
+            message
+                .publish_time
+                .map(format_timestamp)
+                .unwrap_or_default(),
+        ),
+        field("State", message.state.to_string()),
+        field(
+            "Delivery attempt",
+            message
+                .delivery_attempt
+                .map_or("-".to_string(), |n| n.to_string()),
+        ),
+        field("Ordering key", message.ordering_key.clone()),
+    ];
+    if let Some(gap) = messages
+        .selected
+        .and_then(|i| messages.sequence_gaps().get(&i))
+    {
+        lines.push(field("Sequence", gap.clone()).fg(Color::LightRed));
+    }
+    lines.push(field("Attributes", message.attributes.len().to_string()));
+    for (k, v) in &message.attributes {
+        lines.push(Line::raw(format!("  {k} = {v}")));
+    }
+    let size = format!("{} bytes", message.data.len());
+    lines.push(match &message.decoded {
+        Some(Decoded {
+            kind,
+            result: Ok(_),
+        }) => field("Payload", format!("{size}, decoded as {kind}")),
+        Some(Decoded {
+            kind,
+            result: Err(_),
+        }) => field("Payload", format!("{size}, not a valid {kind}")),
+        None => field("Payload", size),
+    });
+    if let Some(Decoded { result: Err(e), .. }) = &message.decoded {
+        lines.push(Line::from(e.clone()).fg(Color::LightRed));
+    }
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    // Attributes can take up to half the pane, the payload gets the rest.
+    let info_height = (lines.len() as u16).min(inner.height / 2);
+    let [info_area, payload_area] =
+        Layout::vertical([Constraint::Length(info_height), Constraint::Min(0)]).areas(inner);
+    let info = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+    f.render_widget(info, info_area);
+    match messages.show_hex {
+        true => hex_view::draw(&messages.hex, f, payload_area),
+        false => json_view::draw(&messages.payload, f, payload_area),
+    }
+}
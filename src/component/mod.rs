@@ -1,5 +1,22 @@
+pub mod bulk_publish;
+pub mod copy;
+pub mod create_subscription;
+pub mod create_topic;
+pub mod dead_letters;
 pub mod debug;
+pub mod decoders;
+pub mod delete_topic;
+pub mod export;
 pub mod header;
+pub mod load_test;
+pub mod messages;
+pub mod probe;
+pub mod publish;
 pub mod pubsub;
 pub mod reusable;
+pub mod schemas;
+pub mod seek;
+pub mod snapshots;
+pub mod subscriptions;
+pub mod tail;
 pub mod topics;
@@ -0,0 +1,578 @@
+use crate::{
+    component::{
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::{send_event, AppEvent},
+    input::{handled, handled_empty, InputHandled},
+    latency::{format_latency, Latencies},
+    util::{parse_rate, short_name},
+};
+use futures::StreamExt;
+use google_cloud_googleapis::pubsub::v1::PubsubMessage;
+use google_cloud_pubsub::{
+    client::Client, publisher::Publisher, subscriber::SubscriberConfig, subscription::Subscription,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Identifies the run a probe message was published by, so that the probe
+/// only acks its own messages.
+const RUN_ATTRIBUTE: &str = "pubsub_cli_probe_run";
+/// When the probe message was published, in microseconds since the epoch.
+const SENT_AT_ATTRIBUTE: &str = "pubsub_cli_probe_sent_at";
+
+/// Publishes timestamped messages to the subscription's topic and measures
+/// how long they take to be received on the subscription. Only the probe's
+/// own messages are acked. Others received meanwhile are left leased, and
+/// are redelivered once their short lease runs out.
+#[derive(Debug, Clone)]
+pub struct ProbeJob {
+    pub subscription: String,
+    pub topic: String,
+    /// Time between probe messages.
+    pub period: Duration,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProbeProgress {
+    pub sent: u64,
+    pub received: u64,
+    pub failed: u64,
+    pub last_error: Option<String>,
+    pub latencies: Latencies,
+    /// Latencies of the current and previous windows, so that changes show
+    /// without being drowned out by the whole run.
+    pub recent: Latencies,
+    pub done: bool,
+}
+
+/// The probe dialog, opened on a subscription, and the probe started from
+/// it which keeps running in a background task when the dialog is closed.
+pub struct Probe {
+    pub subscription: Option<(String, String)>,
+    pub form: Form,
+    pub error: Option<String>,
+    /// Whether the user is being asked to confirm publishing to the topic.
+    pub confirming: bool,
+    pub job: Option<ProbeJob>,
+    pub progress: ProbeProgress,
+    /// The running probe and the signal that stops it, so that it can
+    /// flush its publisher before finishing.
+    task: Option<(JoinHandle<()>, watch::Sender<bool>)>,
+    /// Incremented on every start, so reports from a replaced probe are
+    /// ignored.
+    run: u64,
+}
+
+impl Default for Probe {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            form: Form::new(vec![Field::Text(TextField::new(
+                "rate",
+                "Probe messages per second",
+            ))]),
+            error: None,
+            confirming: false,
+            job: None,
+            progress: ProbeProgress::default(),
+            task: None,
+            run: 0,
+        }
+    }
+}
+
+const DEFAULT_RATE: &str = "10";
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+/// Recent latencies cover between one and two of these.
+const WINDOW: Duration = Duration::from_secs(10);
+/// The lease of messages received on the subscription, the shortest Pub/Sub
+/// allows, so that those the probe leaves alone are redelivered soon.
+const LEASE_SECONDS: i32 = 10;
+const PERCENTILES: [f64; 3] = [50.0, 95.0, 99.0];
+const HISTOGRAM_BOUNDS_MS: [u64; 10] = [1, 2, 5, 10, 20, 50, 100, 200, 500, 1000];
+
+impl Probe {
+    pub fn is_open(&self) -> bool {
+        self.subscription.is_some()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    fn job(&self) -> Result<ProbeJob, String> {
+        let (subscription, topic) = self
+            .subscription
+            .clone()
+            .ok_or("No subscription selected")?;
+        let period = parse_rate(self.form.get("rate"))?
+            .ok_or("The rate must be a positive number of messages per second")?;
+        Ok(ProbeJob {
+            subscription,
+            topic,
+            period,
+        })
+    }
+
+    pub fn start(&mut self, client: Client, job: ProbeJob) {
+        self.stop();
+        self.run += 1;
+        self.progress = ProbeProgress::default();
+        let (stop_tx, stop_rx) = watch::channel(false);
+        self.task = Some((spawn_probe(self.run, client, job.clone(), stop_rx), stop_tx));
+        self.job = Some(job);
+    }
+
+    /// Asks the probe to stop. It reports itself done once its publisher has
+    /// flushed.
+    pub fn stop(&mut self) {
+        if let Some((_, stop)) = &self.task {
+            let _ = stop.send(true);
+        }
+    }
+}
+
+enum Sample {
+    Sent,
+    Failed(String),
+    Received(Duration),
+}
+
+fn micros_since_epoch(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros()
+}
+
+fn spawn_probe(
+    run: u64,
+    client: Client,
+    job: ProbeJob,
+    mut stop: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let run_id = format!(
+            "{}-{}",
+            std::process::id(),
+            micros_since_epoch(SystemTime::now())
+        );
+        let mut progress = ProbeProgress::default();
+        let subscription = client.subscription(&job.subscription);
+        let mut publisher = client.topic(&job.topic).new_publisher(None);
+        let (sample_tx, mut sample_rx) = mpsc::unbounded_channel();
+        let sender = send(
+            publisher.clone(),
+            job.period,
+            run_id.clone(),
+            sample_tx.clone(),
+        );
+        let receiver = receive(subscription, run_id, sample_tx);
+
+        let collector = async {
+            let mut current = Latencies::default();
+            let mut previous = Latencies::default();
+            let mut window_started = Instant::now();
+            let mut ticker = interval(REPORT_INTERVAL);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    sample = sample_rx.recv() => {
+                        match sample {
+                            None => break,
+                            Some(Sample::Sent) => progress.sent += 1,
+                            Some(Sample::Failed(e)) => {
+                                progress.failed += 1;
+                                progress.last_error = Some(e);
+                            }
+                            Some(Sample::Received(latency)) => {
+                                progress.received += 1;
+                                progress.latencies.record(latency);
+                                current.record(latency);
+                            }
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        if window_started.elapsed() >= WINDOW {
+                            previous = std::mem::take(&mut current);
+                            window_started = Instant::now();
+                        }
+                        progress.recent = previous.clone();
+                        progress.recent.merge(&current);
+                        send_event(ProbeEvent::Progress(run, progress.clone()).into()).await;
+                    }
+                }
+            }
+        };
+        // The probe runs until it is stopped, or the subscription can't be
+        // read from.
+        let error = tokio::select! {
+            _ = async { tokio::join!(sender, collector) } => None,
+            error = receiver => error,
+            _ = stop.wait_for(|stop| *stop) => None,
+        };
+        progress.last_error = error.or(progress.last_error);
+        publisher.shutdown().await;
+        progress.done = true;
+        send_event(ProbeEvent::Progress(run, progress).into()).await;
+    })
+}
+
+async fn send(
+    publisher: Publisher,
+    period: Duration,
+    run_id: String,
+    samples: mpsc::UnboundedSender<Sample>,
+) {
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    for sequence in 0u64.. {
+        ticker.tick().await;
+        let sent_at = micros_since_epoch(SystemTime::now());
+        let attributes = HashMap::from([
+            (RUN_ATTRIBUTE.to_string(), run_id.clone()),
+            (SENT_AT_ATTRIBUTE.to_string(), sent_at.to_string()),
+        ]);
+        let awaiter = publisher
+            .publish(PubsubMessage {
+                data: format!("probe {sequence}").into_bytes(),
+                attributes,
+                ..Default::default()
+            })
+            .await;
+        let samples = samples.clone();
+        tokio::spawn(async move {
+            let sample = match awaiter.get().await {
+                Ok(_) => Sample::Sent,
+                Err(e) => Sample::Failed(format!("Failed to publish: {}", e.message())),
+            };
+            let _ = samples.send(sample);
+        });
+    }
+}
+
+/// Acks the messages published by this run and records their latency.
+/// Returns why the subscription could no longer be read from.
+async fn receive(
+    subscription: Subscription,
+    run_id: String,
+    samples: mpsc::UnboundedSender<Sample>,
+) -> Option<String> {
+    let config = SubscriberConfig {
+        stream_ack_deadline_seconds: LEASE_SECONDS,
+        ..Default::default()
+    };
+    let mut stream = match subscription.subscribe(Some(config)).await {
+        Ok(stream) => stream,
+        Err(e) => return Some(format!("Failed to subscribe: {}", e.message())),
+    };
+    while let Some(message) = stream.next().await {
+        let received_at = micros_since_epoch(SystemTime::now());
+        let attributes = &message.message.attributes;
+        if attributes.get(RUN_ATTRIBUTE) != Some(&run_id) {
+            continue;
+        }
+        let sent_at = attributes
+            .get(SENT_AT_ATTRIBUTE)
+            .and_then(|s| s.parse::<u128>().ok());
+        if let Err(e) = message.ack().await {
+            let _ = samples.send(Sample::Failed(format!("Failed to ack: {}", e.message())));
+        }
+        if let Some(sent_at) = sent_at {
+            let latency = received_at.saturating_sub(sent_at);
+            let _ = samples.send(Sample::Received(Duration::from_micros(latency as u64)));
+        }
+    }
+    Some("The subscription stream ended".to_string())
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum ProbeEvent {
+    /// Opens the dialog on a subscription and its topic.
+    Open(String, String),
+    Close,
+    Form(FormEvent),
+    Start,
+    /// Answers whether to publish probe messages to the topic.
+    Confirm(bool),
+    /// A report from the given run of the probe.
+    Progress(u64, ProbeProgress),
+    Stop,
+}
+
+impl From<ProbeEvent> for AppEvent {
+    fn from(event: ProbeEvent) -> Self {
+        AppEvent::Probe(event)
+    }
+}
+
+impl From<FormEvent> for ProbeEvent {
+    fn from(event: FormEvent) -> Self {
+        ProbeEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: ProbeEvent) -> Option<AppEvent> {
+    let probe = &mut state.probe;
+    match event {
+        ProbeEvent::Open(subscription, topic) => {
+            probe.subscription = Some((subscription, topic));
+            probe.error = None;
+            probe.confirming = false;
+            if probe.form.get("rate").is_empty() {
+                probe.form.set("rate", DEFAULT_RATE.to_string());
+            }
+            Some(ProbeEvent::Form(FormEvent::Focus(Some("rate".into()))).into())
+        }
+        ProbeEvent::Close => {
+            probe.subscription = None;
+            probe.confirming = false;
+            probe.form.focused = None;
+            None
+        }
+        ProbeEvent::Form(e) => form::on_event(&mut probe.form, e)
+            .map(ProbeEvent::from)
+            .map(AppEvent::from),
+        ProbeEvent::Start => {
+            match probe.job() {
+                Ok(_) => {
+                    probe.error = None;
+                    probe.form.focused = None;
+                    probe.confirming = true;
+                }
+                Err(e) => probe.error = Some(e),
+            }
+            None
+        }
+        ProbeEvent::Confirm(confirmed) => {
+            probe.confirming = false;
+            match (confirmed, probe.job()) {
+                (true, Ok(job)) => Some(PubsubEvent::StartProbe(Box::new(job)).into()),
+                (true, Err(e)) => {
+                    probe.error = Some(e);
+                    None
+                }
+                (false, _) => None,
+            }
+        }
+        ProbeEvent::Progress(run, progress) => {
+            // Reports still queued from a stopped or replaced probe are
+            // dropped.
+            if !probe.is_running() || run != probe.run {
+                return None;
+            }
+            probe.progress = progress;
+            if probe.progress.done {
+                probe.task = None;
+                state.status.info = Some(match &probe.progress.last_error {
+                    Some(e) => format!("Latency probe stopped: {e}"),
+                    None => "Latency probe stopped".to_string(),
+                });
+            }
+            None
+        }
+        ProbeEvent::Stop => {
+            probe.stop();
+            state.status.info = Some("Stopping the latency probe".to_string());
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Probe, key: KeyEvent) -> InputHandled<AppEvent> {
+    if state.confirming {
+        return match key.code {
+            Char('y') => handled(ProbeEvent::Confirm(true).into()),
+            Char('n') | Esc => handled(ProbeEvent::Confirm(false).into()),
+            _ => handled_empty(),
+        };
+    }
+    let form_handled = form::on_key(&state.form, key)
+        .map(ProbeEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter if !state.is_running() => handled(ProbeEvent::Start.into()),
+        Char('x') if state.is_running() => handled(ProbeEvent::Stop.into()),
+        Esc => handled(ProbeEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to start, Esc to close";
+const RUNNING_HELP: &str = "The probe continues when closed. x to stop, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+fn percentiles(latencies: &Latencies) -> String {
+    PERCENTILES
+        .iter()
+        .map(|p| {
+            let latency = latencies.percentile(*p);
+            format!("p{p} {}", latency.map_or("-".to_string(), format_latency))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// One-line summary for the Subscriptions title while a probe is running.
+pub fn summary(state: &Probe) -> Option<String> {
+    let job = state.job.as_ref().filter(|_| state.is_running())?;
+    Some(format!(
+        "● probing {}: {}",
+        short_name(&job.subscription),
+        percentiles(&state.progress.recent)
+    ))
+}
+
+pub fn draw(state: &Probe, f: &mut Frame, area: Rect) {
+    let subscription = state
+        .subscription
+        .as_ref()
+        .map(|(s, _)| short_name(s))
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Latency probe on {subscription}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area, stats_area, histogram_area] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Length(3),
+        Constraint::Length(7),
+        Constraint::Min(0),
+    ])
+    .margin(1)
+    .areas(area);
+    let mut help = vec![
+        Line::from(match (state.form.is_editing(), state.is_running()) {
+            (true, _) => EDITING_HELP,
+            (false, true) => RUNNING_HELP,
+            (false, false) => VIEWING_HELP,
+        })
+        .fg(Color::Gray),
+    ];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    } else if state.confirming {
+        let topic = state.subscription.as_ref().map(|(_, t)| short_name(t));
+        help.push(
+            Line::from(format!(
+                "Probe messages also reach every other subscription on {}, and other \
+                 messages received meanwhile are delayed up to {LEASE_SECONDS}s. Start? (y/n)",
+                topic.unwrap_or_default()
+            ))
+            .fg(Color::LightRed),
+        );
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+
+    let Some(job) = &state.job else {
+        return;
+    };
+    let progress = &state.progress;
+    let status = match (state.is_running(), progress.done) {
+        (true, _) => "running",
+        (false, _) => "stopped",
+    };
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    let mut lines = vec![
+        field(
+            "Probe",
+            format!(
+                "{} → {} ({status})",
+                short_name(&job.topic),
+                short_name(&job.subscription)
+            ),
+        ),
+        field(
+            "Messages",
+            format!(
+                "{} sent, {} received, {} failed",
+                progress.sent, progress.received, progress.failed
+            ),
+        ),
+        field("Last 10-20s", percentiles(&progress.recent)),
+        field("Whole run", percentiles(&progress.latencies)),
+    ];
+    if let Some(error) = &progress.last_error {
+        lines.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    let stats = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .title("Publish → receive")
+                .borders(Borders::ALL),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(stats, stats_area);
+
+    let bounds = HISTOGRAM_BOUNDS_MS.map(Duration::from_millis);
+    let counts = progress.latencies.histogram(&bounds);
+    let bars: Vec<Bar> = counts
+        .iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let label = match bounds.get(i) {
+                Some(bound) => format!("<{}", format_latency(*bound)),
+                None => format!("≥{}", format_latency(bounds[bounds.len() - 1])),
+            };
+            Bar::default()
+                .value(*count)
+                .label(Line::from(label))
+                .style(Style::default().fg(Color::LightGreen))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .block(Block::default().title("Histogram").borders(Borders::ALL))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(6)
+        .bar_gap(1);
+    f.render_widget(chart, histogram_area);
+}
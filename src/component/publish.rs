@@ -0,0 +1,302 @@
+use crate::{
+    component::{
+        pubsub::PubsubEvent,
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            key_values::{self, KeyValues, KeyValuesEvent},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::short_name,
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Enter, Esc, Tab},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::{HashMap, HashSet};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// A message ready to be handed to the publisher.
+#[derive(Debug, Clone)]
+pub struct OutgoingMessage {
+    pub topic: String,
+    pub data: Vec<u8>,
+    pub attributes: HashMap<String, String>,
+    pub ordering_key: String,
+}
+
+pub struct Composer {
+    pub topic: Option<String>,
+    pub form: Form,
+    pub attributes: KeyValues,
+    /// Whether keys go to the attributes table rather than the form.
+    pub attributes_focused: bool,
+    pub error: Option<String>,
+    /// Why the topic's schema rejected the last payload.
+    pub rejected: Vec<String>,
+    /// Why the last payload couldn't be checked against the topic's schema
+    /// before it was published.
+    pub unchecked: Option<String>,
+    /// Topic and ordering key pairs that failed to publish. Later messages
+    /// with the same key are held back until it is resumed, so they can't
+    /// overtake the failed one.
+    pub paused_keys: HashSet<(String, String)>,
+}
+
+impl Default for Composer {
+    fn default() -> Self {
+        Self {
+            topic: None,
+            form: Form::new(vec![
+                Field::Text(TextField::new("payload", "Payload")),
+                Field::Text(TextField::new("ordering_key", "Ordering key (optional)")),
+            ]),
+            attributes: KeyValues::default(),
+            attributes_focused: false,
+            error: None,
+            rejected: Vec::new(),
+            unchecked: None,
+            paused_keys: HashSet::new(),
+        }
+    }
+}
+
+impl Composer {
+    pub fn is_open(&self) -> bool {
+        self.topic.is_some()
+    }
+
+    /// The topic and ordering key of the message being composed, if that
+    /// key is paused.
+    fn paused_key(&self) -> Option<(String, String)> {
+        let key = (
+            self.topic.clone()?,
+            self.form.get("ordering_key").trim().to_string(),
+        );
+        self.paused_keys.contains(&key).then_some(key)
+    }
+
+    fn message(&self) -> Result<OutgoingMessage, String> {
+        let topic = self.topic.clone().ok_or("No topic selected")?;
+        if let Some((_, key)) = self.paused_key() {
+            return Err(format!(
+                "Ordering key '{key}' is paused after a failed publish, press r to resume it"
+            ));
+        }
+        Ok(OutgoingMessage {
+            topic,
+            data: self.form.get("payload").as_bytes().to_vec(),
+            attributes: self.attributes.to_map()?,
+            ordering_key: self.form.get("ordering_key").trim().to_string(),
+        })
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum ComposerEvent {
+    Open(String),
+    Close,
+    Form(FormEvent),
+    Attributes(KeyValuesEvent),
+    /// Moves the focus between the form and the attributes table.
+    FocusAttributes(bool),
+    Submit,
+    /// The payload does not match the topic's schema.
+    Rejected(Vec<String>),
+    /// The payload was published without a local schema check, and why.
+    Unchecked(String),
+    /// Publishing failed for a message with an ordering key, given with its
+    /// topic and the error.
+    PauseOrderingKey(String, String, String),
+    ResumeOrderingKey,
+}
+
+impl From<ComposerEvent> for AppEvent {
+    fn from(event: ComposerEvent) -> Self {
+        AppEvent::Composer(event)
+    }
+}
+
+impl From<FormEvent> for ComposerEvent {
+    fn from(event: FormEvent) -> Self {
+        ComposerEvent::Form(event)
+    }
+}
+
+impl From<KeyValuesEvent> for ComposerEvent {
+    fn from(event: KeyValuesEvent) -> Self {
+        ComposerEvent::Attributes(event)
+    }
+}
+
+pub fn open_composer(topic: String) -> AppEvent {
+    ComposerEvent::Open(topic).into()
+}
+
+pub fn on_event(state: &mut Composer, event: ComposerEvent) -> Option<AppEvent> {
+    match event {
+        ComposerEvent::Open(topic) => {
+            state.topic = Some(topic);
+            state.error = None;
+            state.rejected.clear();
+            state.unchecked = None;
+            Some(ComposerEvent::FocusAttributes(false).into())
+        }
+        ComposerEvent::Close => {
+            state.topic = None;
+            state.form.focused = None;
+            state.attributes.focused = None;
+            None
+        }
+        ComposerEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(ComposerEvent::from)
+            .map(AppEvent::from),
+        ComposerEvent::Attributes(e) => key_values::on_event(&mut state.attributes, e)
+            .map(ComposerEvent::from)
+            .map(AppEvent::from),
+        ComposerEvent::FocusAttributes(true) => {
+            state.attributes_focused = true;
+            state.form.focused = None;
+            let first = (!state.attributes.rows.is_empty()).then_some((0, key_values::Column::Key));
+            state.attributes.focused = state.attributes.focused.or(first);
+            None
+        }
+        ComposerEvent::FocusAttributes(false) => {
+            state.attributes_focused = false;
+            Some(ComposerEvent::Form(FormEvent::Focus(Some("payload".into()))).into())
+        }
+        ComposerEvent::Submit => match state.message() {
+            Ok(message) => {
+                state.error = None;
+                state.rejected.clear();
+                state.unchecked = None;
+                Some(PubsubEvent::Publish(message).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+        ComposerEvent::Rejected(errors) => {
+            state.error = Some("The payload does not match the topic's schema:".to_string());
+            state.rejected = errors;
+            None
+        }
+        ComposerEvent::Unchecked(reason) => {
+            state.unchecked = Some(reason);
+            None
+        }
+        ComposerEvent::PauseOrderingKey(topic, key, error) => {
+            state.error = Some(format!(
+                "Failed to publish: {error}. Ordering key '{key}' is paused, press r to resume it"
+            ));
+            state.paused_keys.insert((topic, key));
+            None
+        }
+        ComposerEvent::ResumeOrderingKey => {
+            if let Some(key) = state.paused_key() {
+                state.paused_keys.remove(&key);
+                state.error = None;
+            }
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+/// While the composer is open it owns the keyboard, so route and global
+/// shortcuts don't fire while typing a payload.
+pub fn on_key(state: &Composer, key: KeyEvent) -> InputHandled<AppEvent> {
+    let focused_handled = match state.attributes_focused {
+        true => key_values::on_key(&state.attributes, key).map(ComposerEvent::from),
+        false => form::on_key(&state.form, key).map(ComposerEvent::from),
+    };
+    if focused_handled.is_handled() {
+        return focused_handled.map(AppEvent::from);
+    }
+    match key.code {
+        Tab => handled(ComposerEvent::FocusAttributes(!state.attributes_focused).into()),
+        Char('r') if state.paused_key().is_some() => {
+            handled(ComposerEvent::ResumeOrderingKey.into())
+        }
+        Enter => handled(ComposerEvent::Submit.into()),
+        Esc => handled(ComposerEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str =
+    "↑/↓ to navigate, Tab to switch to attributes, Spacebar to edit, Enter to publish, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+const MAX_REJECTIONS: usize = 6;
+
+pub fn draw(state: &Composer, f: &mut Frame, area: Rect) {
+    let topic = state.topic.as_deref().map(short_name).unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Publish to {topic}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let help_height =
+        2 + state.rejected.len().min(MAX_REJECTIONS) as u16 + state.unchecked.is_some() as u16;
+    let [help_area, fields_area, attributes_area] = Layout::vertical([
+        Constraint::Length(help_height),
+        Constraint::Length(6),
+        Constraint::Min(5),
+    ])
+    .margin(1)
+    .areas(area);
+    let editing = state.form.is_editing() || state.attributes.is_editing();
+    let mut help = vec![Line::from(match editing {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    for problem in state.rejected.iter().take(MAX_REJECTIONS) {
+        help.push(Line::from(format!("  {problem}")).fg(Color::LightRed));
+    }
+    if let Some(reason) = &state.unchecked {
+        help.push(
+            Line::from(format!(
+                "Not checked against the schema before publishing: {reason}"
+            ))
+            .fg(Color::Yellow),
+        );
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+    key_values::draw(
+        &state.attributes,
+        "Attributes",
+        state.attributes_focused,
+        f,
+        attributes_area,
+    );
+}
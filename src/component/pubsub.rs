@@ -1,18 +1,54 @@
 use crate::{
+    codec::SchemaCodec,
     component::{
+        bulk_publish::{BulkPublish, BulkPublishJob},
+        copy::{self, Copier, CopyJob},
+        create_subscription::NewSubscription,
+        create_topic::NewTopic,
+        dead_letters::{self, DeadLetters, DeadLettersEvent, Redrive},
         debug::debug_log,
+        decoders::{decode_messages, redecode, schema_key, subscription_topic, Decoders},
+        export::Export,
+        load_test::{LoadTest, LoadTestJob},
+        messages::{AckState, MessageInfo, Messages, MessagesEvent},
+        probe::{Probe, ProbeJob},
+        publish::{ComposerEvent, OutgoingMessage},
         reusable::{
-            choices::{self, Choice, Choices, ChoicesEvent, ChoicesEventType},
-            text_field::{
-                self, draw_simple_text_field, TextField, TextFieldEvent, TextFieldEventType,
-            },
+            choices::{self, Choice, Choices, ChoicesEventType},
+            form::{ConfigField, Field, FieldEvent},
+            text_field::{self, draw_simple_text_field, TextField, TextFieldEventType},
         },
-        topics::{TopicInfo, Topics},
+        schemas::{NewSchema, SchemaInfo, Schemas},
+        seek::SeekEvent,
+        snapshots::{NewSnapshot, SnapshotInfo, Snapshots, SnapshotsEvent},
+        subscriptions::{SubscriptionInfo, Subscriptions},
+        tail::Tail,
+        topics::{TopicDetails, TopicInfo, TopicSchema, Topics},
     },
     event::{send_event, AppEvent},
     input::{handled, not_handled, InputHandled, IntoHandled},
+    util::{format_timestamp, short_name},
+};
+use futures::future::join_all;
+use google_cloud_gax::{
+    conn::{Channel, ConnectionOptions},
+    create_request,
+    grpc::{Code, Status},
+};
+use google_cloud_googleapis::pubsub::v1::{
+    publisher_client::PublisherClient, schema_service_client::SchemaServiceClient,
+    subscriber_client::SubscriberClient, CommitSchemaRequest, CreateSchemaRequest,
+    DeleteSnapshotRequest, Encoding, GetSchemaRequest, GetTopicRequest, ListSchemaRevisionsRequest,
+    ListSchemasRequest, ModifyAckDeadlineRequest, PubsubMessage, RollbackSchemaRequest, Schema,
+    SchemaSettings, SchemaView,
+};
+use google_cloud_pubsub::{
+    apiv1::conn_pool::ConnectionManager,
+    client::{Client, ClientConfig},
+    publisher::Publisher,
+    subscription::{SeekTo, Subscription},
+    topic::TopicConfig,
 };
-use google_cloud_pubsub::client::{Client, ClientConfig};
 use ratatui::{
     crossterm::event::{
         KeyCode::{Down, Esc, Up},
@@ -24,7 +60,11 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 // ======================
 // ==== PUBSUB STATE ====
@@ -33,15 +73,40 @@ use std::collections::HashMap;
 #[derive(Default)]
 pub struct Pubsub {
     client: Option<Client>,
+    grpc: Option<Grpc>,
+    publishers: HashMap<String, Publisher>,
+    pub emulator: bool,
     pub config: PubsubConfig,
     pub status: PubsubStatus,
     pub project_id: Option<String>,
     pub topics: Topics,
+    pub subscriptions: Subscriptions,
+    pub messages: Messages,
+    pub tail: Tail,
+    pub snapshots: Snapshots,
+    pub schemas: Schemas,
+    pub decoders: Decoders,
+    pub bulk: BulkPublish,
+    pub export: Export,
+    pub copier: Copier,
+    pub dead_letters: DeadLetters,
+    pub load_test: LoadTest,
+    pub probe: Probe,
+    /// Schemas seen so far, keyed by name and by `name@revision`, so that
+    /// publishing can validate payloads without fetching them every time.
+    pub schema_cache: HashMap<String, SchemaInfo>,
+    /// Compiled schemas used to validate published payloads, keyed like
+    /// `schema_cache`.
+    codecs: HashMap<String, Arc<SchemaCodec>>,
+    /// The revisions in a topic's schema range, keyed by `name@first..last`,
+    /// so publishing doesn't list them every time.
+    revision_ranges: HashMap<String, Vec<String>>,
 }
 
 pub struct PubsubStatus {
     pub connection: ConnectionStatus,
     pub topics: usize,
+    pub subscriptions: usize,
     pub info: Option<String>,
 }
 
@@ -50,6 +115,7 @@ impl Default for PubsubStatus {
         Self {
             connection: ConnectionStatus::Disconnected,
             topics: 0,
+            subscriptions: 0,
             info: None,
         }
     }
@@ -63,6 +129,57 @@ pub enum ConnectionStatus {
     Connecting,
 }
 
+/// Raw gRPC stubs for the RPCs that `Client` does not expose, such as
+/// fetching a topic's configuration, nacking by ack ID or managing schemas.
+#[derive(Clone)]
+struct Grpc {
+    publisher: PublisherClient<Channel>,
+    subscriber: SubscriberClient<Channel>,
+    schema: SchemaServiceClient<Channel>,
+}
+
+impl Grpc {
+    async fn new(config: &ClientConfig) -> anyhow::Result<Self> {
+        let cm = ConnectionManager::new(
+            1,
+            &config.endpoint,
+            &config.environment,
+            &ConnectionOptions::default(),
+        )
+        .await?;
+        Ok(Self {
+            publisher: PublisherClient::new(cm.conn()),
+            subscriber: SubscriberClient::new(cm.conn()),
+            schema: SchemaServiceClient::new(cm.conn()),
+        })
+    }
+
+    /// Every revision of a schema, newest first.
+    async fn schema_revisions(&self, name: &str) -> Result<Vec<SchemaInfo>, Status> {
+        let mut revisions = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let req = ListSchemaRevisionsRequest {
+                name: name.to_string(),
+                view: SchemaView::Full as i32,
+                page_size: 0,
+                page_token,
+            };
+            let response = self
+                .schema
+                .clone()
+                .list_schema_revisions(create_request(format!("name={name}"), req))
+                .await?
+                .into_inner();
+            revisions.extend(response.schemas.into_iter().map(SchemaInfo::from));
+            if response.next_page_token.is_empty() {
+                return Ok(revisions);
+            }
+            page_token = response.next_page_token;
+        }
+    }
+}
+
 impl Pubsub {
     pub async fn new(
         project_id: String,
@@ -80,13 +197,32 @@ impl Pubsub {
             config.endpoint = format!("{host}:{port}")
         }
         config.project_id = Some(project_id.clone());
+        let grpc = Grpc::new(&config).await?;
         let client = Client::new(config).await?;
         Ok(Self {
             client: Some(client),
+            grpc: Some(grpc),
+            publishers: HashMap::new(),
+            emulator,
             config: PubsubConfig::default(),
             status: PubsubStatus::default(),
             project_id: Some(project_id.to_string()),
             topics: Topics::new(),
+            subscriptions: Subscriptions::default(),
+            messages: Messages::default(),
+            tail: Tail::default(),
+            snapshots: Snapshots::default(),
+            schemas: Schemas::default(),
+            decoders: Decoders::default(),
+            bulk: BulkPublish::default(),
+            export: Export::default(),
+            copier: Copier::default(),
+            dead_letters: DeadLetters::default(),
+            load_test: LoadTest::default(),
+            probe: Probe::default(),
+            schema_cache: HashMap::new(),
+            codecs: HashMap::new(),
+            revision_ranges: HashMap::new(),
         })
     }
 }
@@ -95,108 +231,6 @@ impl Pubsub {
 // ==== CONFIG STATE ====
 // ======================
 
-trait ConfigField {
-    fn is_editing(&self) -> bool {
-        false
-    }
-    fn name(&self) -> &String;
-    fn label(&self) -> &String;
-    fn as_text(&self) -> Option<&String> {
-        None
-    }
-    fn as_choices(&self) -> Option<&[Choice]> {
-        None
-    }
-    fn value(&self) -> &String;
-    fn set_value(&mut self, value: String);
-}
-
-impl ConfigField for TextField {
-    fn name(&self) -> &String {
-        &self.name
-    }
-    fn label(&self) -> &String {
-        &self.label
-    }
-    fn value(&self) -> &String {
-        &self.value
-    }
-    fn as_text(&self) -> Option<&String> {
-        Some(&self.value)
-    }
-    fn set_value(&mut self, value: String) {
-        self.set_value(value);
-    }
-}
-
-impl ConfigField for Choices {
-    fn name(&self) -> &String {
-        &self.name
-    }
-    fn label(&self) -> &String {
-        &self.label
-    }
-    fn value(&self) -> &String {
-        &self.value
-    }
-    fn as_choices(&self) -> Option<&[Choice]> {
-        Some(&self.choices)
-    }
-    fn set_value(&mut self, value: String) {
-        self.choose_index(self.choices.iter().position(|c| c.value == value));
-    }
-}
-
-enum Field {
-    Text(TextField),
-    Choices(Choices),
-}
-
-impl ConfigField for Field {
-    fn name(&self) -> &String {
-        match self {
-            Field::Text(f) => f.name(),
-            Field::Choices(f) => f.name(),
-        }
-    }
-    fn label(&self) -> &String {
-        match self {
-            Field::Text(f) => f.label(),
-            Field::Choices(f) => f.label(),
-        }
-    }
-    fn value(&self) -> &String {
-        match self {
-            Field::Text(f) => f.value(),
-            Field::Choices(f) => f.value(),
-        }
-    }
-    fn as_text(&self) -> Option<&String> {
-        match self {
-            Field::Text(f) => f.as_text(),
-            _ => None,
-        }
-    }
-    fn as_choices(&self) -> Option<&[Choice]> {
-        match self {
-            Field::Choices(f) => f.as_choices(),
-            _ => None,
-        }
-    }
-    fn set_value(&mut self, value: String) {
-        match self {
-            Field::Text(f) => f.set_value(value),
-            Field::Choices(f) => f.set_value(value),
-        }
-    }
-    fn is_editing(&self) -> bool {
-        match self {
-            Field::Text(f) => f.is_editing,
-            Field::Choices(f) => f.is_editing,
-        }
-    }
-}
-
 pub struct PubsubConfig {
     fields: HashMap<String, Field>,
     pub focused: Option<String>,
@@ -207,21 +241,21 @@ impl Default for PubsubConfig {
         let mut fields = HashMap::new();
         fields.insert(
             "project_id".to_string(),
-            Field::Text(TextField::new("project_id".into(), "Project ID".into())),
+            Field::Text(TextField::new("project_id", "Project ID")),
         );
         fields.insert(
             "host".to_string(),
-            Field::Text(TextField::new("host".into(), "Host".into())),
+            Field::Text(TextField::new("host", "Host")),
         );
         fields.insert(
             "port".to_string(),
-            Field::Text(TextField::new("port".into(), "Port".into())),
+            Field::Text(TextField::new("port", "Port")),
         );
         fields.insert(
             "emulator".to_string(),
             Field::Choices(Choices::new(
-                "emulator".into(),
-                "Emulator".into(),
+                "emulator",
+                "Emulator",
                 vec![
                     Choice {
                         label: "Yes".into(),
@@ -265,6 +299,7 @@ impl PubsubConfig {
         field.set_value(value);
     }
 
+    #[allow(dead_code)]
     fn get_text_field(&self, name: &str) -> &TextField {
         match self.fields.get(name) {
             Some(Field::Text(f)) => f,
@@ -272,6 +307,7 @@ impl PubsubConfig {
         }
     }
 
+    #[allow(dead_code)]
     fn get_choices_field(&self, name: &str) -> &Choices {
         match self.fields.get(name) {
             Some(Field::Choices(f)) => f,
@@ -308,9 +344,53 @@ pub fn init_config(state: &mut PubsubConfig) {
 pub enum PubsubEvent {
     Connect,
     GetTopics,
+    GetSubscriptions,
+    GetTopicDetails(String),
+    Publish(OutgoingMessage),
+    /// The topic a message was sent to and its ID, or why it failed.
+    Published(String, Result<String, String>),
+    StartBulkPublish(Box<BulkPublishJob>),
+    StartCopy(Box<CopyJob>),
+    StartLoadTest(Box<LoadTestJob>),
+    StartProbe(Box<ProbeJob>),
+    /// Pulls into the dead-letter inspector rather than the Messages buffer.
+    PullDeadLetters(String, i32),
+    /// Keeps the dead letters' leases from running out while they are shown.
+    ExtendDeadLetterLeases(String, Vec<String>),
+    Redrive(Box<Redrive>),
+    CreateTopic(NewTopic),
+    DeleteTopic(String),
+    CreateSubscription(Box<NewSubscription>),
+    GetSnapshots,
+    CreateSnapshot(NewSnapshot),
+    DeleteSnapshot(String),
+    GetSchemas,
+    GetSchemaRevisions(String),
+    CreateSchema(NewSchema),
+    CommitSchema(String, NewSchema),
+    /// Schema name and the revision ID to roll back to.
+    RollbackSchema(String, String),
+    Seek(String, SeekTo),
+    /// What seeking the subscription did to its backlog, or why it failed.
+    Seeked(String, Result<String, String>),
+    Pull(String, i32),
+    /// Fetches what decoding a subscription's buffered messages needs.
+    FetchDecodingSchemas(String),
+    Ack(String, Vec<String>),
+    Nack(String, Vec<String>),
+    ExtendLease(String, Vec<String>),
+    StartTail(String),
     Config(ConfigEvent),
     ChangeProjectId(String),
     GotTopics(Vec<TopicInfo>),
+    GotSubscriptions(Vec<SubscriptionInfo>),
+    GotTopicDetails(String, Box<TopicDetails>),
+    GotMessages(String, Vec<MessageInfo>),
+    /// Why a pull brought back no messages.
+    PullFailed(String),
+    GotSnapshots(Vec<SnapshotInfo>),
+    GotSchemas(Vec<SchemaInfo>),
+    GotSchemaRevisions(String, Vec<SchemaInfo>),
 }
 
 pub fn set_project_id(id: String) -> PubsubEvent {
@@ -325,24 +405,6 @@ async fn connect_to_pubsub() {
 // ==== CONFIG EVENTS ====
 // =======================
 
-#[derive(Debug, Clone)]
-pub enum FieldEvent {
-    TextFieldEvent(TextFieldEvent),
-    ChoicesEvent(ChoicesEvent),
-}
-
-impl From<TextFieldEvent> for FieldEvent {
-    fn from(event: TextFieldEvent) -> Self {
-        FieldEvent::TextFieldEvent(event)
-    }
-}
-
-impl From<ChoicesEvent> for FieldEvent {
-    fn from(event: ChoicesEvent) -> Self {
-        FieldEvent::ChoicesEvent(event)
-    }
-}
-
 #[derive(Debug, Clone)]
 pub enum ConfigEvent {
     ConfigFieldEvent(FieldEvent),
@@ -369,10 +431,7 @@ fn unfocus() -> ConfigEvent {
 
 pub async fn on_event(state: &mut Pubsub, e: PubsubEvent) -> Option<AppEvent> {
     match e {
-        PubsubEvent::Connect => {
-            on_connect_to_pubsub(state).await;
-            None
-        }
+        PubsubEvent::Connect => on_connect_to_pubsub(state).await,
         PubsubEvent::ChangeProjectId(id) => {
             on_change_project_id(state, id).await;
             None
@@ -387,8 +446,188 @@ pub async fn on_event(state: &mut Pubsub, e: PubsubEvent) -> Option<AppEvent> {
             state.topics.set_topics(topics);
             None
         }
-    };
-    None
+        PubsubEvent::GetSubscriptions => on_get_subscriptions(state).await,
+        PubsubEvent::GotSubscriptions(subscriptions) => {
+            state.status.subscriptions = subscriptions.len();
+            state.subscriptions.set_subscriptions(subscriptions);
+            None
+        }
+        PubsubEvent::GetTopicDetails(name) => on_get_topic_details(state, name).await,
+        PubsubEvent::Publish(message) => on_publish(state, message).await,
+        PubsubEvent::Published(topic, result) => {
+            state.status.info = Some(match result {
+                Ok(id) => format!("Published message {} to {}", id, short_name(&topic)),
+                Err(e) => format!("Failed to publish: {e}"),
+            });
+            None
+        }
+        PubsubEvent::StartBulkPublish(job) => {
+            let Some(client) = &state.client else {
+                state.status.info = Some("Not connected to Pub/Sub".to_string());
+                return None;
+            };
+            let topic = client.topic(&job.topic);
+            let file = job.file.display().to_string();
+            state.bulk.start(topic, *job);
+            state.status.info = Some(format!("Publishing {file}"));
+            None
+        }
+        PubsubEvent::StartCopy(job) => {
+            match (&state.client, &state.grpc) {
+                (Some(client), Some(grpc)) => {
+                    let source = client.subscription(&job.source);
+                    let destination = match job.endpoint {
+                        Some(_) => None,
+                        None => Some(client.topic(&job.destination)),
+                    };
+                    state.status.info = Some(format!(
+                        "Copying {} to {}",
+                        short_name(&job.source),
+                        short_name(&job.destination)
+                    ));
+                    let leases = grpc.subscriber.clone();
+                    state.copier.start(source, destination, leases, *job);
+                }
+                _ => state.status.info = Some("Not connected to Pub/Sub".to_string()),
+            }
+            None
+        }
+        PubsubEvent::StartLoadTest(job) => {
+            match &state.client {
+                Some(client) => {
+                    let topic = client.topic(&job.topic);
+                    state.status.info = Some(format!("Load testing {}", short_name(&job.topic)));
+                    state.load_test.start(topic, *job);
+                }
+                None => state.status.info = Some("Not connected to Pub/Sub".to_string()),
+            }
+            None
+        }
+        PubsubEvent::StartProbe(job) => {
+            match &state.client {
+                Some(client) => {
+                    state.status.info = Some(format!("Probing {}", short_name(&job.topic)));
+                    state.probe.start(client.clone(), *job);
+                }
+                None => state.status.info = Some("Not connected to Pub/Sub".to_string()),
+            }
+            None
+        }
+        PubsubEvent::PullDeadLetters(subscription, max) => {
+            on_pull_dead_letters(state, subscription, max)
+        }
+        PubsubEvent::ExtendDeadLetterLeases(subscription, ack_ids) => {
+            on_extend_dead_letter_leases(state, subscription, ack_ids)
+        }
+        PubsubEvent::Redrive(redrive) => on_redrive(state, *redrive),
+        PubsubEvent::CreateTopic(topic) => on_create_topic(state, topic).await,
+        PubsubEvent::DeleteTopic(name) => on_delete_topic(state, name).await,
+        PubsubEvent::CreateSubscription(sub) => on_create_subscription(state, *sub).await,
+        PubsubEvent::GetSnapshots => on_get_snapshots(state).await,
+        PubsubEvent::GotSnapshots(snapshots) => {
+            state.snapshots.set_snapshots(snapshots.clone());
+            Some(SeekEvent::SetSnapshots(snapshots).into())
+        }
+        PubsubEvent::CreateSnapshot(snapshot) => on_create_snapshot(state, snapshot).await,
+        PubsubEvent::DeleteSnapshot(name) => on_delete_snapshot(state, name).await,
+        PubsubEvent::GetSchemas => on_get_schemas(state).await,
+        PubsubEvent::GotSchemas(schemas) => {
+            for schema in &schemas {
+                state
+                    .schema_cache
+                    .insert(schema.name.clone(), schema.clone());
+                // The schema may have a new revision since it was compiled.
+                state.codecs.remove(&schema.name);
+            }
+            // A range open at either end may now take more revisions.
+            state.revision_ranges.clear();
+            let reload = state.schemas.selected().is_none();
+            state.schemas.set_schemas(schemas);
+            match (reload, state.schemas.selected()) {
+                (true, Some(schema)) => {
+                    Some(PubsubEvent::GetSchemaRevisions(schema.name.clone()).into())
+                }
+                _ => None,
+            }
+        }
+        PubsubEvent::GetSchemaRevisions(name) => on_get_schema_revisions(state, name).await,
+        PubsubEvent::GotSchemaRevisions(name, revisions) => {
+            for revision in &revisions {
+                let key = format!("{}@{}", name, revision.revision_id);
+                state.schema_cache.insert(key, revision.clone());
+            }
+            state.schemas.set_revisions(&name, revisions);
+            None
+        }
+        PubsubEvent::CreateSchema(schema) => on_create_schema(state, schema).await,
+        PubsubEvent::CommitSchema(name, schema) => on_commit_schema(state, name, schema).await,
+        PubsubEvent::RollbackSchema(name, revision_id) => {
+            on_rollback_schema(state, name, revision_id).await
+        }
+        PubsubEvent::Seek(subscription, to) => on_seek(state, subscription, to),
+        PubsubEvent::Seeked(subscription, result) => {
+            match result {
+                Ok(report) => {
+                    state.status.info = Some(report);
+                    // Outstanding ack IDs are invalidated by the seek, so
+                    // there is nothing to nack.
+                    if state.messages.subscription.as_ref() == Some(&subscription) {
+                        state.messages.clear();
+                    }
+                }
+                Err(e) => state.status.info = Some(e),
+            }
+            None
+        }
+        PubsubEvent::Pull(subscription, max) => on_pull(state, subscription, max),
+        PubsubEvent::GotMessages(subscription, mut messages) => {
+            let incomplete = decode_messages(state, &subscription, &mut messages);
+            state.status.info = Some(format!(
+                "Pulled {} messages from {}",
+                messages.len(),
+                short_name(&subscription)
+            ));
+            // Messages left leased on the previous subscription or dropped
+            // from the buffer are nacked.
+            for (released, ack_ids) in state.messages.add_messages(subscription.clone(), messages) {
+                tokio::spawn(send_event(PubsubEvent::Nack(released, ack_ids).into()));
+            }
+            incomplete.then(|| PubsubEvent::FetchDecodingSchemas(subscription).into())
+        }
+        PubsubEvent::PullFailed(reason) => {
+            state.status.info = Some(reason);
+            None
+        }
+        PubsubEvent::FetchDecodingSchemas(subscription) => {
+            on_fetch_decoding_schemas(state, subscription).await
+        }
+        PubsubEvent::StartTail(subscription) => {
+            match &state.client {
+                Some(client) => {
+                    state.tail.start(client.subscription(&subscription));
+                    state.status.info = Some(format!("Tailing {}", short_name(&subscription)));
+                }
+                None => state.status.info = Some("Not connected to Pub/Sub".to_string()),
+            }
+            None
+        }
+        PubsubEvent::Ack(subscription, ack_ids) => on_ack(state, subscription, ack_ids).await,
+        PubsubEvent::Nack(subscription, ack_ids) => {
+            on_modify_ack_deadline(state, subscription, ack_ids, 0).await
+        }
+        PubsubEvent::ExtendLease(subscription, ack_ids) => {
+            on_modify_ack_deadline(state, subscription, ack_ids, LEASE_EXTENSION_SECONDS).await
+        }
+        PubsubEvent::GotTopicDetails(name, details) => {
+            state
+                .decoders
+                .topic_schemas
+                .insert(name.clone(), details.schema.clone());
+            state.decoders.unavailable_topics.remove(&name);
+            state.topics.set_details(&name, *details);
+            None
+        }
+    }
 }
 
 async fn on_change_project_id(state: &mut Pubsub, id: String) {
@@ -403,24 +642,36 @@ async fn on_change_project_id(state: &mut Pubsub, id: String) {
     connect_to_pubsub().await;
 }
 
-async fn on_connect_to_pubsub(state: &mut Pubsub) {
+async fn on_connect_to_pubsub(state: &mut Pubsub) -> Option<AppEvent> {
     if let Some(id) = state.project_id.as_ref() {
         match Pubsub::new(id.clone(), "localhost".to_string(), 8065, true).await {
             Ok(pubsub) => {
+                state.tail.stop();
+                for (_, mut publisher) in state.publishers.drain() {
+                    publisher.shutdown().await;
+                }
                 state.client = pubsub.client;
+                state.grpc = pubsub.grpc;
+                state.emulator = pubsub.emulator;
                 state.status.connection = ConnectionStatus::Connected;
                 state.status.info = Some("Connected to Pub/Sub".to_string());
+                send_event(PubsubEvent::GetTopics.into()).await;
+                send_event(PubsubEvent::GetSnapshots.into()).await;
+                send_event(PubsubEvent::GetSchemas.into()).await;
+                Some(PubsubEvent::GetSubscriptions.into())
             }
             Err(e) => {
                 state.client = None;
+                state.grpc = None;
                 state.status.connection = ConnectionStatus::Disconnected;
                 state.status.info = Some(format!("Failed to connect: {}", e));
+                None
             }
-        };
+        }
     } else {
         debug_log("Project ID is empty!!".to_string());
         state.status.info = Some("Project ID is empty".to_string());
-        return;
+        None
     }
 }
 
@@ -428,8 +679,7 @@ async fn on_get_topics(state: &mut Pubsub) -> Option<AppEvent> {
     if let Some(client) = &state.client {
         match client.get_topics(None).await {
             Ok(topics) => {
-                let topic_infos: Vec<TopicInfo> =
-                    topics.into_iter().map(|t| TopicInfo { name: t }).collect();
+                let topic_infos: Vec<TopicInfo> = topics.into_iter().map(TopicInfo::new).collect();
                 Some(PubsubEvent::GotTopics(topic_infos).into())
             }
             Err(e) => {
@@ -444,6 +694,1021 @@ async fn on_get_topics(state: &mut Pubsub) -> Option<AppEvent> {
     }
 }
 
+async fn on_get_topic_details(state: &mut Pubsub, name: String) -> Option<AppEvent> {
+    let (Some(client), Some(grpc)) = (&state.client, &state.grpc) else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let req = GetTopicRequest {
+        topic: name.clone(),
+    };
+    let topic = match grpc
+        .publisher
+        .clone()
+        .get_topic(create_request(format!("topic={name}"), req))
+        .await
+    {
+        Ok(response) => response.into_inner(),
+        Err(e) => {
+            state.status.info = Some(format!("Failed to get topic {}: {}", name, e.message()));
+            return None;
+        }
+    };
+    let subscriptions = match client.topic(&name).subscriptions(None).await {
+        Ok(subscriptions) => subscriptions
+            .iter()
+            .map(|s| s.fully_qualified_name().to_string())
+            .collect(),
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to get subscriptions for {}: {}",
+                name,
+                e.message()
+            ));
+            return None;
+        }
+    };
+    let details = TopicDetails {
+        labels: topic.labels.into_iter().collect(),
+        message_retention: topic
+            .message_retention_duration
+            .and_then(|d| d.try_into().ok()),
+        schema: topic.schema_settings.map(TopicSchema::from),
+        kms_key_name: topic.kms_key_name,
+        allowed_persistence_regions: topic
+            .message_storage_policy
+            .map(|p| p.allowed_persistence_regions)
+            .unwrap_or_default(),
+        subscriptions,
+    };
+    Some(PubsubEvent::GotTopicDetails(name, Box::new(details)).into())
+}
+
+async fn on_create_topic(state: &mut Pubsub, topic: NewTopic) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let project_id = state.project_id.clone().unwrap_or_default();
+    let config = TopicConfig {
+        labels: topic.labels,
+        message_retention_duration: topic.message_retention,
+        schema_settings: topic.schema.map(|(schema, encoding)| SchemaSettings {
+            schema: match schema.contains('/') {
+                true => schema,
+                false => format!("projects/{}/schemas/{}", project_id, schema),
+            },
+            encoding: encoding as i32,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    match client.create_topic(&topic.id, Some(config), None).await {
+        Ok(_) => {
+            state.status.info = Some(format!("Created topic {}", topic.id));
+            Some(PubsubEvent::GetTopics.into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to create topic {}: {}",
+                topic.id,
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+/// Subscriptions on the topic survive as detached (`_deleted-topic_`), so
+/// both lists are reloaded.
+async fn on_delete_topic(state: &mut Pubsub, name: String) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    match client.topic(&name).delete(None).await {
+        Ok(()) => {
+            if let Some(mut publisher) = state.publishers.remove(&name) {
+                publisher.shutdown().await;
+            }
+            state.status.info = Some(format!("Deleted topic {}", short_name(&name)));
+            send_event(PubsubEvent::GetTopics.into()).await;
+            Some(PubsubEvent::GetSubscriptions.into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to delete topic {}: {}",
+                short_name(&name),
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+async fn on_create_subscription(state: &mut Pubsub, sub: NewSubscription) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let mut config = sub.config;
+    if let Some(policy) = config.dead_letter_policy.as_mut() {
+        policy.dead_letter_topic = client.fully_qualified_topic_name(&policy.dead_letter_topic);
+    }
+    match client
+        .create_subscription(&sub.id, &sub.topic, config, None)
+        .await
+    {
+        Ok(_) => {
+            state.status.info = Some(format!(
+                "Created subscription {} on {}",
+                sub.id,
+                short_name(&sub.topic)
+            ));
+            // Refresh the topic's details so its subscription list includes the new one.
+            send_event(PubsubEvent::GetTopicDetails(sub.topic).into()).await;
+            Some(PubsubEvent::GetSubscriptions.into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to create subscription {}: {}",
+                sub.id,
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+async fn on_get_snapshots(state: &mut Pubsub) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    match client.get_snapshots(None).await {
+        Ok(snapshots) => {
+            let snapshots = snapshots.into_iter().map(SnapshotInfo::from).collect();
+            Some(PubsubEvent::GotSnapshots(snapshots).into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!("Failed to get snapshots: {}", e.message()));
+            None
+        }
+    }
+}
+
+async fn on_create_snapshot(state: &mut Pubsub, snapshot: NewSnapshot) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    match client
+        .subscription(&snapshot.subscription)
+        .create_snapshot(&snapshot.id, snapshot.labels, None)
+        .await
+    {
+        Ok(created) => {
+            state.status.info = Some(format!(
+                "Created snapshot {} of {}",
+                snapshot.id,
+                short_name(&snapshot.subscription)
+            ));
+            Some(SnapshotsEvent::Created(created.name, snapshot.subscription).into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to create snapshot {}: {}",
+                snapshot.id,
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+async fn on_delete_snapshot(state: &mut Pubsub, name: String) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let req = DeleteSnapshotRequest {
+        snapshot: name.clone(),
+    };
+    let result = grpc
+        .subscriber
+        .clone()
+        .delete_snapshot(create_request(format!("snapshot={name}"), req))
+        .await;
+    state.status.info = Some(match result {
+        Ok(_) => {
+            send_event(PubsubEvent::GetSnapshots.into()).await;
+            format!("Deleted snapshot {}", short_name(&name))
+        }
+        Err(e) => format!(
+            "Failed to delete snapshot {}: {}",
+            short_name(&name),
+            e.message()
+        ),
+    });
+    Some(SnapshotsEvent::ConfirmDelete(None).into())
+}
+
+async fn on_get_schemas(state: &mut Pubsub) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let parent = format!("projects/{}", state.project_id.clone().unwrap_or_default());
+    let mut schemas = Vec::new();
+    let mut page_token = String::new();
+    loop {
+        let req = ListSchemasRequest {
+            parent: parent.clone(),
+            view: SchemaView::Full as i32,
+            page_size: 0,
+            page_token,
+        };
+        match grpc
+            .schema
+            .clone()
+            .list_schemas(create_request(format!("parent={parent}"), req))
+            .await
+        {
+            Ok(response) => {
+                let response = response.into_inner();
+                schemas.extend(response.schemas.into_iter().map(SchemaInfo::from));
+                if response.next_page_token.is_empty() {
+                    break;
+                }
+                page_token = response.next_page_token;
+            }
+            Err(e) => {
+                state.status.info = Some(format!("Failed to get schemas: {}", e.message()));
+                return None;
+            }
+        }
+    }
+    Some(PubsubEvent::GotSchemas(schemas).into())
+}
+
+async fn on_get_schema_revisions(state: &mut Pubsub, name: String) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    match grpc.schema_revisions(&name).await {
+        Ok(revisions) => Some(PubsubEvent::GotSchemaRevisions(name, revisions).into()),
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to get revisions of {}: {}",
+                short_name(&name),
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+async fn on_create_schema(state: &mut Pubsub, schema: NewSchema) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let parent = format!("projects/{}", state.project_id.clone().unwrap_or_default());
+    let req = CreateSchemaRequest {
+        parent: parent.clone(),
+        schema: Some(Schema {
+            r#type: schema.kind as i32,
+            definition: schema.definition,
+            ..Default::default()
+        }),
+        schema_id: schema.id.clone(),
+    };
+    match grpc
+        .schema
+        .clone()
+        .create_schema(create_request(format!("parent={parent}"), req))
+        .await
+    {
+        Ok(_) => {
+            state.status.info = Some(format!("Created schema {}", schema.id));
+            Some(PubsubEvent::GetSchemas.into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to create schema {}: {}",
+                schema.id,
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+async fn on_commit_schema(state: &mut Pubsub, name: String, schema: NewSchema) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let req = CommitSchemaRequest {
+        name: name.clone(),
+        schema: Some(Schema {
+            name: name.clone(),
+            r#type: schema.kind as i32,
+            definition: schema.definition,
+            ..Default::default()
+        }),
+    };
+    match grpc
+        .schema
+        .clone()
+        .commit_schema(create_request(format!("name={name}"), req))
+        .await
+    {
+        Ok(response) => {
+            state.status.info = Some(format!(
+                "Committed revision {} of {}",
+                response.into_inner().revision_id,
+                short_name(&name)
+            ));
+            send_event(PubsubEvent::GetSchemas.into()).await;
+            Some(PubsubEvent::GetSchemaRevisions(name).into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to commit {}: {}",
+                short_name(&name),
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+/// Rolling back commits a copy of the old revision as the newest one.
+async fn on_rollback_schema(
+    state: &mut Pubsub,
+    name: String,
+    revision_id: String,
+) -> Option<AppEvent> {
+    state.schemas.rolling_back = None;
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let req = RollbackSchemaRequest {
+        name: name.clone(),
+        revision_id: revision_id.clone(),
+    };
+    match grpc
+        .schema
+        .clone()
+        .rollback_schema(create_request(format!("name={name}"), req))
+        .await
+    {
+        Ok(response) => {
+            state.status.info = Some(format!(
+                "Rolled {} back to {} as revision {}",
+                short_name(&name),
+                revision_id,
+                response.into_inner().revision_id
+            ));
+            send_event(PubsubEvent::GetSchemas.into()).await;
+            Some(PubsubEvent::GetSchemaRevisions(name).into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!(
+                "Failed to roll back {}: {}",
+                short_name(&name),
+                e.message()
+            ));
+            None
+        }
+    }
+}
+
+/// Most messages counted on each side of a seek, as counting pulls them.
+const BACKLOG_COUNT_CAP: usize = 5000;
+/// How long a counting pull waits before the backlog is taken to be empty.
+const BACKLOG_PULL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Seeks in the background, the result arriving as `Seeked` with how the
+/// subscription's backlog changed.
+fn on_seek(state: &mut Pubsub, subscription: String, to: SeekTo) -> Option<AppEvent> {
+    let (Some(client), Some(grpc)) = (&state.client, &state.grpc) else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let target = client.subscription(&subscription);
+    let leases = grpc.subscriber.clone();
+    let name = short_name(&subscription).to_string();
+    let label = match &to {
+        SeekTo::Snapshot(snapshot) => format!("snapshot {}", short_name(snapshot)),
+        SeekTo::Timestamp(time) => format_timestamp(*time),
+    };
+    state.status.info = Some(format!("Seeking {name} to {label}"));
+    tokio::spawn(async move {
+        let result = seek(&target, to, leases)
+            .await
+            .map(|change| format!("Seeked {name} to {label}. Backlog: {change}"));
+        send_event(PubsubEvent::Seeked(subscription, result).into()).await;
+    });
+    None
+}
+
+/// Seeks `target` and returns how its backlog changed, counted before and
+/// after the seek.
+async fn seek(
+    target: &Subscription,
+    to: SeekTo,
+    mut leases: SubscriberClient<Channel>,
+) -> Result<String, String> {
+    let (_, config) = target
+        .config(None)
+        .await
+        .map_err(|e| format!("Failed to seek: {}", e.message()))?;
+    // Every message counted gets another delivery attempt, which would bring
+    // it closer to being dead-lettered.
+    let countable = config.dead_letter_policy.is_none();
+    let before = match countable {
+        true => Some(count_backlog(target, &mut leases).await?),
+        false => None,
+    };
+    target
+        .seek(to, None)
+        .await
+        .map_err(|e| format!("Failed to seek: {}", e.message()))?;
+    let Some(before) = before else {
+        return Ok(
+            "not counted, as that would add delivery attempts towards dead-lettering".into(),
+        );
+    };
+    let after = count_backlog(target, &mut leases).await?;
+    let count = |n: usize| match n >= BACKLOG_COUNT_CAP {
+        true => format!("{BACKLOG_COUNT_CAP}+"),
+        false => n.to_string(),
+    };
+    Ok(format!("{} → {} messages", count(before), count(after)))
+}
+
+/// Counts the messages `target` delivers right now, up to
+/// `BACKLOG_COUNT_CAP`, by pulling them and releasing them straight away.
+/// Messages leased elsewhere, such as in the Messages view, aren't counted.
+async fn count_backlog(
+    target: &Subscription,
+    leases: &mut SubscriberClient<Channel>,
+) -> Result<usize, String> {
+    let mut ack_ids = Vec::new();
+    let mut error = None;
+    while ack_ids.len() < BACKLOG_COUNT_CAP {
+        let max = (BACKLOG_COUNT_CAP - ack_ids.len()).min(1000) as i32;
+        match tokio::time::timeout(BACKLOG_PULL_TIMEOUT, target.pull(max, None)).await {
+            Ok(Ok(received)) if !received.is_empty() => {
+                ack_ids.extend(received.iter().map(|m| m.ack_id().to_string()))
+            }
+            Ok(Ok(_)) | Err(_) => break,
+            Ok(Err(e)) => {
+                error = Some(format!("Failed to count the backlog: {}", e.message()));
+                break;
+            }
+        }
+    }
+    copy::modify_leases(leases, target.fully_qualified_name(), &ack_ids, 0)
+        .await
+        .map_err(|e| format!("Failed to release the counted messages: {}", e.message()))?;
+    match error {
+        Some(e) => Err(e),
+        None => Ok(ack_ids.len()),
+    }
+}
+
+/// Compiles the schema revisions a topic accepts messages for, newest first,
+/// using the cached topic settings, schemas and codecs when there are any.
+/// Returns `None` if the topic has no schema, or why a revision couldn't be
+/// fetched or compiled.
+async fn topic_codecs(
+    state: &mut Pubsub,
+    topic: &str,
+) -> Result<Option<(Vec<(String, Arc<SchemaCodec>)>, Encoding)>, String> {
+    let grpc = state.grpc.clone().ok_or("Not connected to Pub/Sub")?;
+    if !state.decoders.topic_schemas.contains_key(topic) {
+        let req = GetTopicRequest {
+            topic: topic.to_string(),
+        };
+        let settings = grpc
+            .publisher
+            .clone()
+            .get_topic(create_request(format!("topic={topic}"), req))
+            .await
+            .map_err(|e| format!("Failed to get the topic's schema: {}", e.message()))?
+            .into_inner()
+            .schema_settings
+            .map(TopicSchema::from);
+        state
+            .decoders
+            .topic_schemas
+            .insert(topic.to_string(), settings);
+        state.decoders.unavailable_topics.remove(topic);
+    }
+    let Some(Some(settings)) = state.decoders.topic_schemas.get(topic).cloned() else {
+        return Ok(None);
+    };
+    let encoding = Encoding::from_str_name(&settings.encoding).unwrap_or(Encoding::Unspecified);
+    let mut codecs = Vec::new();
+    for name in topic_revisions(state, &grpc, &settings).await? {
+        let codec = match state.codecs.get(&name) {
+            Some(codec) => codec.clone(),
+            None => {
+                let schema = schema_revision(state, &grpc, &name).await?;
+                let codec = Arc::new(SchemaCodec::compile(&schema).map_err(|e| {
+                    format!("Schema {} doesn't compile locally: {e}", short_name(&name))
+                })?);
+                state.codecs.insert(name.clone(), codec.clone());
+                codec
+            }
+        };
+        codecs.push((name, codec));
+    }
+    Ok(Some((codecs, encoding)))
+}
+
+/// The `name@revision` keys of the revisions in the topic's range, newest
+/// first. An empty bound leaves that end of the range open, so unless the
+/// range is a single revision the schema's revisions are listed.
+async fn topic_revisions(
+    state: &mut Pubsub,
+    grpc: &Grpc,
+    settings: &TopicSchema,
+) -> Result<Vec<String>, String> {
+    let (first, last) = (&settings.first_revision_id, &settings.last_revision_id);
+    if !last.is_empty() && first == last {
+        return Ok(vec![format!("{}@{last}", settings.name)]);
+    }
+    let range = format!("{}@{first}..{last}", settings.name);
+    if let Some(keys) = state.revision_ranges.get(&range) {
+        return Ok(keys.clone());
+    }
+    let revisions = grpc.schema_revisions(&settings.name).await.map_err(|e| {
+        format!(
+            "Failed to get revisions of {}: {}",
+            short_name(&settings.name),
+            e.message()
+        )
+    })?;
+    let mut keys = Vec::new();
+    let mut in_range = last.is_empty();
+    for revision in revisions {
+        in_range |= &revision.revision_id == last;
+        if !in_range {
+            continue;
+        }
+        let key = format!("{}@{}", settings.name, revision.revision_id);
+        let done = &revision.revision_id == first;
+        state.schema_cache.insert(key.clone(), revision);
+        keys.push(key);
+        if done {
+            break;
+        }
+    }
+    if keys.is_empty() {
+        return Err(format!(
+            "No revisions of {} are in the topic's range",
+            short_name(&settings.name)
+        ));
+    }
+    state.revision_ranges.insert(range, keys.clone());
+    Ok(keys)
+}
+
+async fn schema_revision(
+    state: &mut Pubsub,
+    grpc: &Grpc,
+    name: &str,
+) -> Result<SchemaInfo, String> {
+    if let Some(schema) = state.schema_cache.get(name) {
+        return Ok(schema.clone());
+    }
+    let req = GetSchemaRequest {
+        name: name.to_string(),
+        view: SchemaView::Full as i32,
+    };
+    let schema = SchemaInfo::from(
+        grpc.schema
+            .clone()
+            .get_schema(create_request(format!("name={name}"), req))
+            .await
+            .map_err(|e| format!("Failed to get schema {}: {}", short_name(name), e.message()))?
+            .into_inner(),
+    );
+    state.schema_cache.insert(name.to_string(), schema.clone());
+    Ok(schema)
+}
+
+async fn on_publish(state: &mut Pubsub, message: OutgoingMessage) -> Option<AppEvent> {
+    // A schema that can't be checked locally is still checked by the
+    // server, so the message is published with a warning. The topic takes
+    // messages matching any revision in its range, so it is rejected only
+    // if none of them accept it.
+    match topic_codecs(state, &message.topic).await {
+        Ok(Some((codecs, encoding))) => {
+            let several = codecs.len() > 1;
+            let mut errors = Vec::new();
+            for (name, codec) in codecs {
+                match codec.validate(encoding, &message.data) {
+                    Ok(()) => {
+                        errors.clear();
+                        break;
+                    }
+                    Err(found) if several => errors.extend(
+                        found
+                            .into_iter()
+                            .map(|e| format!("{}: {e}", short_name(&name))),
+                    ),
+                    Err(found) => errors.extend(found),
+                }
+            }
+            if !errors.is_empty() {
+                return Some(ComposerEvent::Rejected(errors).into());
+            }
+        }
+        Ok(None) => {}
+        Err(e) => send_event(ComposerEvent::Unchecked(e).into()).await,
+    }
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let publisher = state
+        .publishers
+        .entry(message.topic.clone())
+        .or_insert_with(|| client.topic(&message.topic).new_publisher(None));
+    let awaiter = publisher
+        .publish(PubsubMessage {
+            data: message.data,
+            attributes: message.attributes,
+            ordering_key: message.ordering_key.clone(),
+            ..Default::default()
+        })
+        .await;
+    // The server's answer can take a while, so it is waited for in the
+    // background.
+    tokio::spawn(async move {
+        let event = match awaiter.get().await {
+            Ok(id) => PubsubEvent::Published(message.topic, Ok(id)).into(),
+            // Schema validation failures come back as invalid arguments.
+            Err(e) if e.code() == Code::InvalidArgument => {
+                ComposerEvent::Rejected(vec![e.message().to_string()]).into()
+            }
+            Err(e) if !message.ordering_key.is_empty() => ComposerEvent::PauseOrderingKey(
+                message.topic,
+                message.ordering_key,
+                e.message().to_string(),
+            )
+            .into(),
+            Err(e) => PubsubEvent::Published(message.topic, Err(e.message().to_string())).into(),
+        };
+        send_event(event).await;
+    });
+    None
+}
+
+/// Fetches the schema settings of the subscription's topic and the schema
+/// revisions its buffered messages were published with, then decodes the
+/// buffer again. Anything that can't be fetched is not asked for again.
+async fn on_fetch_decoding_schemas(state: &mut Pubsub, subscription: String) -> Option<AppEvent> {
+    let grpc = state.grpc.clone()?;
+    let topic = subscription_topic(state, &subscription)?.to_string();
+    // A failure isn't cached in `topic_schemas`, where it would read as the
+    // topic having no schema.
+    if !state.decoders.topic_schemas.contains_key(&topic)
+        && !state.decoders.unavailable_topics.contains(&topic)
+    {
+        let req = GetTopicRequest {
+            topic: topic.clone(),
+        };
+        match grpc
+            .publisher
+            .clone()
+            .get_topic(create_request(format!("topic={topic}"), req))
+            .await
+        {
+            Ok(response) => {
+                let settings = response.into_inner().schema_settings.map(TopicSchema::from);
+                state.decoders.topic_schemas.insert(topic.clone(), settings);
+            }
+            Err(e) => {
+                state.status.info = Some(format!(
+                    "Failed to get the schema of {}: {}",
+                    short_name(&topic),
+                    e.message()
+                ));
+                state.decoders.unavailable_topics.insert(topic.clone());
+            }
+        }
+    }
+    if let Some(Some(schema)) = state.decoders.topic_schemas.get(&topic) {
+        let keys: HashSet<String> = state
+            .messages
+            .all
+            .iter()
+            .map(|m| schema_key(schema, m))
+            .filter(|k| !state.schema_cache.contains_key(k))
+            .filter(|k| !state.decoders.unavailable.contains(k))
+            .collect();
+        for key in keys {
+            let req = GetSchemaRequest {
+                name: key.clone(),
+                view: SchemaView::Full as i32,
+            };
+            match grpc
+                .schema
+                .clone()
+                .get_schema(create_request(format!("name={key}"), req))
+                .await
+            {
+                Ok(response) => {
+                    let schema = SchemaInfo::from(response.into_inner());
+                    state.schema_cache.insert(key, schema);
+                }
+                Err(e) => {
+                    state.status.info = Some(format!(
+                        "Failed to get schema {}: {}",
+                        short_name(&key),
+                        e.message()
+                    ));
+                    state.decoders.unavailable.insert(key);
+                }
+            }
+        }
+    }
+    redecode(state);
+    None
+}
+
+/// How long `Pull` waits for messages. The RPC otherwise blocks until at
+/// least one message is available.
+const PULL_TIMEOUT: Duration = Duration::from_secs(5);
+const LEASE_EXTENSION_SECONDS: i32 = 60;
+
+/// Pulls in the background, the result arriving as `GotMessages` or
+/// `PullFailed`.
+fn on_pull(state: &mut Pubsub, subscription: String, max: i32) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let target = client.subscription(&subscription);
+    state.status.info = Some(format!("Pulling from {}", short_name(&subscription)));
+    tokio::spawn(async move {
+        let event = match tokio::time::timeout(PULL_TIMEOUT, target.pull(max, None)).await {
+            Ok(Ok(received)) => {
+                let messages = received.iter().map(MessageInfo::from).collect();
+                PubsubEvent::GotMessages(subscription, messages)
+            }
+            Ok(Err(e)) => PubsubEvent::PullFailed(format!("Failed to pull: {}", e.message())),
+            Err(_) => PubsubEvent::PullFailed(format!(
+                "No messages available on {}",
+                short_name(&subscription)
+            )),
+        };
+        send_event(event.into()).await;
+    });
+    None
+}
+
+/// Pulls in the background like `on_pull`, the result arriving as
+/// `DeadLettersEvent::Got` or `DeadLettersEvent::PullFailed`.
+fn on_pull_dead_letters(state: &mut Pubsub, subscription: String, max: i32) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let target = client.subscription(&subscription);
+    state.status.info = Some(format!(
+        "Pulling dead letters from {}",
+        short_name(&subscription)
+    ));
+    tokio::spawn(async move {
+        let event = match tokio::time::timeout(PULL_TIMEOUT, target.pull(max, None)).await {
+            Ok(Ok(received)) => {
+                let messages = received.iter().map(MessageInfo::from).collect();
+                DeadLettersEvent::Got(subscription, messages)
+            }
+            Ok(Err(e)) => DeadLettersEvent::PullFailed(format!("Failed to pull: {}", e.message())),
+            Err(_) => DeadLettersEvent::PullFailed(format!(
+                "No dead letters available on {}",
+                short_name(&subscription)
+            )),
+        };
+        send_event(event.into()).await;
+    });
+    None
+}
+
+fn on_extend_dead_letter_leases(
+    state: &mut Pubsub,
+    subscription: String,
+    ack_ids: Vec<String>,
+) -> Option<AppEvent> {
+    let Some(grpc) = state.grpc.clone() else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    tokio::spawn(async move {
+        let extended =
+            Instant::now() + Duration::from_secs(dead_letters::LEASE_EXTENSION_SECONDS as u64);
+        let req = ModifyAckDeadlineRequest {
+            subscription: subscription.clone(),
+            ack_ids,
+            ack_deadline_seconds: dead_letters::LEASE_EXTENSION_SECONDS,
+        };
+        match grpc
+            .subscriber
+            .clone()
+            .modify_ack_deadline(create_request(format!("subscription={subscription}"), req))
+            .await
+        {
+            Ok(_) => send_event(DeadLettersEvent::Leased(Ok(extended)).into()).await,
+            Err(e) => {
+                let error = e.message().to_string();
+                send_event(DeadLettersEvent::Leased(Err(error)).into()).await
+            }
+        }
+    });
+    None
+}
+
+/// Publishes each message back to its topic with its original attributes,
+/// then acks the ones that were published on the dead-letter subscription.
+/// Runs in the background and reports back with `Redriven`.
+fn on_redrive(state: &mut Pubsub, redrive: Redrive) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        let error = Some("Not connected to Pub/Sub".to_string());
+        return Some(DeadLettersEvent::Redriven(Vec::new(), error).into());
+    };
+    let publishers: Vec<Publisher> = redrive
+        .by_topic
+        .keys()
+        .map(|topic| {
+            state
+                .publishers
+                .entry(topic.clone())
+                .or_insert_with(|| client.topic(topic).new_publisher(None))
+                .clone()
+        })
+        .collect();
+    let dead_letter_subscription = client.subscription(&redrive.dead_letter_subscription);
+    tokio::spawn(async move {
+        let mut published = Vec::new();
+        let mut error = None;
+        for ((topic, messages), publisher) in redrive.by_topic.iter().zip(publishers) {
+            let mut awaiters = Vec::new();
+            for message in messages {
+                let original = dead_letters::original(message);
+                let awaiter = publisher
+                    .publish(PubsubMessage {
+                        data: original.data,
+                        attributes: original.attributes.into_iter().collect(),
+                        ordering_key: original.ordering_key,
+                        ..Default::default()
+                    })
+                    .await;
+                awaiters.push((message.ack_id.clone(), awaiter));
+            }
+            for (ack_id, awaiter) in awaiters {
+                match awaiter.get().await {
+                    Ok(_) => published.push(ack_id),
+                    Err(e) => {
+                        error.get_or_insert_with(|| {
+                            format!(
+                                "Failed to publish to {}: {}",
+                                short_name(topic),
+                                e.message()
+                            )
+                        });
+                    }
+                }
+            }
+        }
+        if !published.is_empty() {
+            if let Err(e) = dead_letter_subscription.ack(published.clone()).await {
+                // They were published, so they leave the list even though the
+                // dead-letter subscription will deliver them again.
+                error = Some(format!(
+                    "Published, but failed to ack the dead letters: {}",
+                    e.message()
+                ));
+            }
+        }
+        send_event(DeadLettersEvent::Redriven(published, error).into()).await;
+    });
+    None
+}
+
+async fn on_ack(
+    state: &mut Pubsub,
+    subscription: String,
+    ack_ids: Vec<String>,
+) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let count = ack_ids.len();
+    match client
+        .subscription(&subscription)
+        .ack(ack_ids.clone())
+        .await
+    {
+        Ok(()) => {
+            state.status.info = Some(format!("Acked {} messages", count));
+            Some(MessagesEvent::SetState(ack_ids, AckState::Acked).into())
+        }
+        Err(e) => {
+            state.status.info = Some(format!("Failed to ack: {}", e.message()));
+            None
+        }
+    }
+}
+
+/// A deadline of 0 nacks the messages; anything else extends their lease.
+async fn on_modify_ack_deadline(
+    state: &mut Pubsub,
+    subscription: String,
+    ack_ids: Vec<String>,
+    ack_deadline_seconds: i32,
+) -> Option<AppEvent> {
+    let Some(grpc) = &state.grpc else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let count = ack_ids.len();
+    let req = ModifyAckDeadlineRequest {
+        subscription: subscription.clone(),
+        ack_ids: ack_ids.clone(),
+        ack_deadline_seconds,
+    };
+    let result = grpc
+        .subscriber
+        .clone()
+        .modify_ack_deadline(create_request(format!("subscription={subscription}"), req))
+        .await;
+    match (result, ack_deadline_seconds) {
+        (Ok(_), 0) => {
+            state.status.info = Some(format!("Nacked {} messages", count));
+            Some(MessagesEvent::SetState(ack_ids, AckState::Nacked).into())
+        }
+        (Ok(_), seconds) => {
+            state.status.info = Some(format!(
+                "Extended lease of {} messages by {}s",
+                count, seconds
+            ));
+            None
+        }
+        (Err(e), _) => {
+            state.status.info = Some(format!("Failed to modify ack deadline: {}", e.message()));
+            None
+        }
+    }
+}
+
+async fn on_get_subscriptions(state: &mut Pubsub) -> Option<AppEvent> {
+    let Some(client) = &state.client else {
+        state.status.info = Some("Not connected to Pub/Sub".to_string());
+        return None;
+    };
+    let subscriptions = match client.get_subscriptions(None).await {
+        Ok(subscriptions) => subscriptions,
+        Err(e) => {
+            state.status.info = Some(format!("Failed to get subscriptions: {}", e));
+            return None;
+        }
+    };
+    // A subscription whose config can't be read, e.g. deleted while
+    // listing, is left out rather than hiding the others.
+    let configs = join_all(subscriptions.iter().map(|s| s.config(None))).await;
+    let mut infos = Vec::with_capacity(subscriptions.len());
+    let mut failed = Vec::new();
+    for (subscription, config) in subscriptions.iter().zip(configs) {
+        match config {
+            Ok((topic, config)) => infos.push(SubscriptionInfo::new(
+                subscription.fully_qualified_name().to_string(),
+                topic,
+                &config,
+            )),
+            Err(e) => failed.push(format!("{}: {}", subscription.id(), e)),
+        }
+    }
+    if !failed.is_empty() {
+        state.status.info = Some(format!(
+            "Failed to get the config of {} subscriptions: {}",
+            failed.len(),
+            failed.join(", ")
+        ));
+    }
+    Some(PubsubEvent::GotSubscriptions(infos).into())
+}
+
 pub async fn on_config_event(state: &mut PubsubConfig, e: ConfigEvent) {
     match e {
         ConfigEvent::ConfigFieldEvent(e) => on_config_field_event(state, e).await,
@@ -527,8 +1792,8 @@ fn on_text_field_key(state: &PubsubConfig, key: KeyEvent) -> InputHandled<FieldE
 fn on_arrow_key(state: &PubsubConfig, key: KeyEvent) -> InputHandled<ConfigEvent> {
     let field_names = DEFAULT_FIELD_ORDER;
 
-    if matches!(state.focused, None) {
-        return handled(focus(field_names[0]).into());
+    if state.focused.is_none() {
+        return handled(focus(field_names[0]));
     }
 
     let current_index = field_names
@@ -647,10 +1912,11 @@ pub fn draw_pubsub_status(state: &Pubsub, f: &mut Frame, area: Rect) {
         .clone()
         .unwrap_or_else(|| "No info".to_string());
     let topics_count = state.status.topics;
+    let subscriptions_count = state.status.subscriptions;
 
     let paragraph = Paragraph::new(format!(
-        "Status: {} Topics: {} Info: {}",
-        status_text, topics_count, info_text
+        "Status: {} Topics: {} Subscriptions: {} Info: {}",
+        status_text, topics_count, subscriptions_count, info_text
     ))
     .wrap(Wrap { trim: true });
 
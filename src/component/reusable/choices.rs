@@ -152,6 +152,7 @@ pub fn on_event(state: &mut Choices, event: ChoicesEventType) -> Option<ChoicesE
 // ==== INPUT ====
 // ===============
 
+#[allow(clippy::useless_conversion)]
 pub fn on_key(state: &Choices, key: KeyEvent) -> InputHandled<ChoicesEvent> {
     match state.is_editing {
         true => match key.code {
@@ -0,0 +1,317 @@
+use crate::{
+    component::reusable::{
+        choices::{self, Choice, Choices, ChoicesEvent, ChoicesEventType},
+        text_field::{self, draw_simple_text_field, TextField, TextFieldEvent, TextFieldEventType},
+    },
+    input::{handled, not_handled, InputHandled},
+};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Down, Up},
+        KeyEvent,
+    },
+    layout::Rect,
+    Frame,
+};
+use std::collections::HashMap;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[allow(dead_code)]
+pub trait ConfigField {
+    fn is_editing(&self) -> bool {
+        false
+    }
+    fn name(&self) -> &String;
+    fn label(&self) -> &String;
+    fn as_text(&self) -> Option<&String> {
+        None
+    }
+    fn as_choices(&self) -> Option<&[Choice]> {
+        None
+    }
+    fn value(&self) -> &String;
+    fn set_value(&mut self, value: String);
+}
+
+impl ConfigField for TextField {
+    fn name(&self) -> &String {
+        &self.name
+    }
+    fn label(&self) -> &String {
+        &self.label
+    }
+    fn value(&self) -> &String {
+        &self.value
+    }
+    fn as_text(&self) -> Option<&String> {
+        Some(&self.value)
+    }
+    fn set_value(&mut self, value: String) {
+        self.set_value(value);
+    }
+}
+
+impl ConfigField for Choices {
+    fn name(&self) -> &String {
+        &self.name
+    }
+    fn label(&self) -> &String {
+        &self.label
+    }
+    fn value(&self) -> &String {
+        &self.value
+    }
+    fn as_choices(&self) -> Option<&[Choice]> {
+        Some(&self.choices)
+    }
+    fn set_value(&mut self, value: String) {
+        self.choose_index(self.choices.iter().position(|c| c.value == value));
+    }
+}
+
+pub enum Field {
+    Text(TextField),
+    Choices(Choices),
+}
+
+impl ConfigField for Field {
+    fn name(&self) -> &String {
+        match self {
+            Field::Text(f) => f.name(),
+            Field::Choices(f) => f.name(),
+        }
+    }
+    fn label(&self) -> &String {
+        match self {
+            Field::Text(f) => f.label(),
+            Field::Choices(f) => f.label(),
+        }
+    }
+    fn value(&self) -> &String {
+        match self {
+            Field::Text(f) => f.value(),
+            Field::Choices(f) => f.value(),
+        }
+    }
+    fn as_text(&self) -> Option<&String> {
+        match self {
+            Field::Text(f) => f.as_text(),
+            _ => None,
+        }
+    }
+    fn as_choices(&self) -> Option<&[Choice]> {
+        match self {
+            Field::Choices(f) => f.as_choices(),
+            _ => None,
+        }
+    }
+    fn set_value(&mut self, value: String) {
+        match self {
+            Field::Text(f) => f.set_value(value),
+            Field::Choices(f) => f.set_value(value),
+        }
+    }
+    fn is_editing(&self) -> bool {
+        match self {
+            Field::Text(f) => f.is_editing,
+            Field::Choices(f) => f.is_editing,
+        }
+    }
+}
+
+/// A vertical stack of fields, navigated with ↑/↓ and edited with the
+/// field's own key handling. Fields are drawn in the order they were given.
+#[derive(Default)]
+pub struct Form {
+    fields: HashMap<String, Field>,
+    order: Vec<String>,
+    pub focused: Option<String>,
+}
+
+impl Form {
+    pub fn new(fields: Vec<Field>) -> Self {
+        let order = fields.iter().map(|f| f.name().clone()).collect();
+        let fields = fields.into_iter().map(|f| (f.name().clone(), f)).collect();
+        Self {
+            fields,
+            order,
+            focused: None,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> &String {
+        let field = self.fields.get(name).unwrap_or_else(|| {
+            panic!("Unknown field: {}", name);
+        });
+        field.value()
+    }
+
+    pub fn set(&mut self, name: &str, value: String) {
+        let field = self.fields.get_mut(name).unwrap_or_else(|| {
+            panic!("Unknown field: {}", name);
+        });
+        field.set_value(value);
+    }
+
+    /// What is being typed into a text field, or its value when it isn't
+    /// being edited.
+    pub fn typed(&self, name: &str) -> &str {
+        match self.fields.get(name) {
+            Some(Field::Text(field)) if field.is_editing => &field.input,
+            _ => self.get(name),
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.focused
+            .as_ref()
+            .and_then(|name| self.fields.get(name))
+            .is_some_and(|f| f.is_editing())
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum FieldEvent {
+    TextFieldEvent(TextFieldEvent),
+    ChoicesEvent(ChoicesEvent),
+}
+
+impl From<TextFieldEvent> for FieldEvent {
+    fn from(event: TextFieldEvent) -> Self {
+        FieldEvent::TextFieldEvent(event)
+    }
+}
+
+impl From<ChoicesEvent> for FieldEvent {
+    fn from(event: ChoicesEvent) -> Self {
+        FieldEvent::ChoicesEvent(event)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum FormEvent {
+    Field(FieldEvent),
+    Focus(Option<String>),
+}
+
+impl From<FieldEvent> for FormEvent {
+    fn from(event: FieldEvent) -> Self {
+        FormEvent::Field(event)
+    }
+}
+
+impl FormEvent {
+    /// The name of the field whose value was just committed, if any.
+    pub fn value_changed(&self) -> Option<&str> {
+        match self {
+            FormEvent::Field(FieldEvent::TextFieldEvent(TextFieldEvent {
+                name,
+                event_type: TextFieldEventType::ValueChanged,
+            })) => Some(name),
+            FormEvent::Field(FieldEvent::ChoicesEvent(ChoicesEvent {
+                name,
+                event_type: ChoicesEventType::ValueChanged,
+            })) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+// ==================
+// ==== HANDLERS ====
+// ==================
+
+pub fn on_event(state: &mut Form, e: FormEvent) -> Option<FormEvent> {
+    match e {
+        FormEvent::Field(FieldEvent::TextFieldEvent(e)) => match state.fields.get_mut(&e.name) {
+            Some(Field::Text(field)) => text_field::on_event(field, e.event_type)
+                .map(FieldEvent::from)
+                .map(FormEvent::from),
+            _ => None,
+        },
+        FormEvent::Field(FieldEvent::ChoicesEvent(e)) => match state.fields.get_mut(&e.name) {
+            Some(Field::Choices(field)) => choices::on_event(field, e.event_type)
+                .map(FieldEvent::from)
+                .map(FormEvent::from),
+            _ => None,
+        },
+        FormEvent::Focus(name) => {
+            state.focused = name;
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Form, key: KeyEvent) -> InputHandled<FormEvent> {
+    let field_handled = match state.focused.as_ref().and_then(|n| state.fields.get(n)) {
+        Some(Field::Text(field)) => text_field::on_key(field, key).map(FieldEvent::from),
+        Some(Field::Choices(field)) => choices::on_key(field, key).map(FieldEvent::from),
+        None => not_handled(),
+    }
+    .map(FormEvent::from);
+    if field_handled.is_handled() || state.order.is_empty() {
+        return field_handled;
+    }
+
+    let count = state.order.len();
+    let current = state
+        .focused
+        .as_ref()
+        .and_then(|n| state.order.iter().position(|o| o == n));
+    let next = match key.code {
+        Up => current.map_or(count - 1, |i| (i + count - 1) % count),
+        Down => current.map_or(0, |i| (i + 1) % count),
+        _ => return not_handled(),
+    };
+    handled(FormEvent::Focus(Some(state.order[next].clone())))
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const FIELD_HEIGHT: u16 = 3;
+const CHOICES_WIDTH: u16 = 24;
+
+pub fn draw(state: &Form, f: &mut Frame, area: Rect) {
+    let visible = (area.height / FIELD_HEIGHT).max(1) as usize;
+    let focused_idx = state
+        .focused
+        .as_ref()
+        .and_then(|n| state.order.iter().position(|o| o == n))
+        .unwrap_or(0);
+    let skip = (focused_idx + 1).saturating_sub(visible);
+
+    let mut open_choices = None;
+    for (i, name) in state.order.iter().skip(skip).take(visible).enumerate() {
+        let y = area.y + i as u16 * FIELD_HEIGHT;
+        let is_focused = state.focused.as_ref() == Some(name);
+        match &state.fields[name] {
+            Field::Text(t) => {
+                draw_simple_text_field(t, is_focused, f, Rect::new(area.x, y, area.width, 1))
+            }
+            Field::Choices(c) => {
+                let rect = Rect::new(area.x, y, CHOICES_WIDTH.min(area.width), 1);
+                choices::draw(c, is_focused, f, rect);
+                if c.is_editing {
+                    open_choices = Some((c, rect));
+                }
+            }
+        }
+    }
+    // Draw an open dropdown last so later fields don't paint over it.
+    if let Some((c, rect)) = open_choices {
+        choices::draw(c, true, f, rect);
+    }
+}
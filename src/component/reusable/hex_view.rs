@@ -0,0 +1,206 @@
+use crate::{
+    component::reusable::text_field::{self, TextField, TextFieldEventType},
+    input::{handled, not_handled, InputHandled},
+};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+const BYTES_PER_ROW: usize = 16;
+
+/// A scrollable hex dump with an offset column and an ASCII gutter.
+pub struct HexView {
+    data: Vec<u8>,
+    /// The first row shown.
+    pub top: usize,
+    /// The byte last jumped to, which is highlighted.
+    pub mark: Option<usize>,
+    pub jump: TextField,
+    pub error: Option<String>,
+}
+
+impl Default for HexView {
+    fn default() -> Self {
+        Self {
+            data: Vec::new(),
+            top: 0,
+            mark: None,
+            jump: TextField::new("offset", "Jump to offset"),
+            error: None,
+        }
+    }
+}
+
+impl HexView {
+    /// Shows `data`, returning `false` if it was already shown.
+    pub fn set_data(&mut self, data: &[u8]) -> bool {
+        if self.data == data {
+            return false;
+        }
+        self.data = data.to_vec();
+        self.top = 0;
+        self.mark = None;
+        self.error = None;
+        true
+    }
+
+    fn rows(&self) -> usize {
+        self.data.len().div_ceil(BYTES_PER_ROW)
+    }
+
+    fn jump_to(&mut self, input: &str) {
+        let input = input.trim();
+        let parsed = match input.strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => input.parse(),
+        };
+        match parsed {
+            Ok(offset) if offset < self.data.len() => {
+                self.top = offset / BYTES_PER_ROW;
+                self.mark = Some(offset);
+                self.error = None;
+            }
+            Ok(offset) => {
+                self.error = Some(format!(
+                    "Offset {offset} is past the end ({} bytes)",
+                    self.data.len()
+                ))
+            }
+            Err(_) => self.error = Some(format!("'{input}' is not an offset")),
+        }
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum HexViewEvent {
+    Scroll(isize),
+    Jump(TextFieldEventType),
+}
+
+// ==================
+// ==== HANDLERS ====
+// ==================
+
+pub fn on_event(state: &mut HexView, event: HexViewEvent) -> Option<HexViewEvent> {
+    match event {
+        HexViewEvent::Scroll(rows) => {
+            let last = state.rows().saturating_sub(1);
+            state.top = state.top.saturating_add_signed(rows).min(last);
+            None
+        }
+        HexViewEvent::Jump(TextFieldEventType::ValueChanged) => {
+            let input = state.jump.value.clone();
+            state.jump_to(&input);
+            None
+        }
+        HexViewEvent::Jump(e) => {
+            text_field::on_event(&mut state.jump, e).map(|e| HexViewEvent::Jump(e.event_type))
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+const PAGE: isize = 16;
+
+pub fn on_key(state: &HexView, key: KeyEvent) -> InputHandled<HexViewEvent> {
+    if state.jump.is_editing {
+        return text_field::on_key(&state.jump, key).map(|e| HexViewEvent::Jump(e.event_type));
+    }
+    match key.code {
+        KeyCode::Char('j') => handled(HexViewEvent::Scroll(1)),
+        KeyCode::Char('k') => handled(HexViewEvent::Scroll(-1)),
+        KeyCode::PageDown => handled(HexViewEvent::Scroll(PAGE)),
+        KeyCode::PageUp => handled(HexViewEvent::Scroll(-PAGE)),
+        KeyCode::Char('g') => handled(HexViewEvent::Jump(TextFieldEventType::StartEditing)),
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str = "j/k scroll, PgUp/PgDn page, g jump to offset (decimal or 0x..)";
+
+pub fn draw(state: &HexView, f: &mut Frame, area: Rect) {
+    let [body_area, footer_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+    let lines: Vec<Line> = state
+        .data
+        .chunks(BYTES_PER_ROW)
+        .enumerate()
+        .skip(state.top)
+        .take(body_area.height as usize)
+        .map(|(row, chunk)| row_line(row * BYTES_PER_ROW, chunk, state.mark))
+        .collect();
+    f.render_widget(Paragraph::new(lines), body_area);
+
+    let footer = if state.jump.is_editing {
+        Line::from(vec![
+            Span::styled("Jump to offset: ", Style::default().fg(Color::LightCyan)),
+            Span::styled(state.jump.input.clone(), Style::default().bold().yellow()),
+        ])
+    } else if let Some(error) = &state.error {
+        Line::from(error.as_str()).fg(Color::LightRed)
+    } else {
+        let shown = (state.top * BYTES_PER_ROW).min(state.data.len());
+        Line::from(format!("{shown:#x} of {} bytes  {HELP}", state.data.len())).fg(Color::Gray)
+    };
+    f.render_widget(Paragraph::new(footer), footer_area);
+    if state.jump.is_editing {
+        #[allow(clippy::cast_possible_truncation)]
+        f.set_cursor_position(Position::new(
+            footer_area.x + "Jump to offset: ".len() as u16 + state.jump.character_index as u16,
+            footer_area.y,
+        ));
+    }
+}
+
+fn row_line(offset: usize, chunk: &[u8], mark: Option<usize>) -> Line<'static> {
+    let marked = |i: usize| match mark == Some(offset + i) {
+        true => Style::default().bg(Color::LightYellow).fg(Color::Black),
+        false => Style::default(),
+    };
+    let mut spans = vec![Span::styled(
+        format!("{offset:08x}  "),
+        Style::default().fg(Color::DarkGray),
+    )];
+    for i in 0..BYTES_PER_ROW {
+        let hex = match chunk.get(i) {
+            Some(byte) => Span::styled(format!("{byte:02x}"), marked(i)),
+            None => Span::raw("  "),
+        };
+        spans.push(hex);
+        // An extra gap after eight bytes makes offsets easier to count.
+        spans.push(Span::raw(if i == 7 { "  " } else { " " }));
+    }
+    spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
+    for (i, &byte) in chunk.iter().enumerate() {
+        let (c, color) = match byte.is_ascii_graphic() || byte == b' ' {
+            true => (byte as char, Color::LightGreen),
+            false => ('.', Color::DarkGray),
+        };
+        let style = match mark == Some(offset + i) {
+            true => marked(i),
+            false => Style::default().fg(color),
+        };
+        spans.push(Span::styled(c.to_string(), style));
+    }
+    Line::from(spans)
+}
@@ -0,0 +1,182 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span, Text},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Language {
+    /// JSON, which also covers Avro schema definitions.
+    Json,
+    Protobuf,
+}
+
+const PROTO_KEYWORDS: &[&str] = &[
+    "syntax",
+    "package",
+    "import",
+    "option",
+    "message",
+    "enum",
+    "oneof",
+    "map",
+    "repeated",
+    "optional",
+    "required",
+    "reserved",
+    "extensions",
+    "to",
+    "max",
+    "service",
+    "rpc",
+    "returns",
+    "stream",
+    "public",
+    "weak",
+];
+
+const PROTO_TYPES: &[&str] = &[
+    "double", "float", "int32", "int64", "uint32", "uint64", "sint32", "sint64", "fixed32",
+    "fixed64", "sfixed32", "sfixed64", "bool", "string", "bytes",
+];
+
+pub const KEY: Style = Style::new().fg(Color::LightCyan);
+pub const STRING: Style = Style::new().fg(Color::LightGreen);
+pub const NUMBER: Style = Style::new().fg(Color::LightMagenta);
+pub const KEYWORD: Style = Style::new().fg(Color::LightBlue);
+const TYPE: Style = Style::new().fg(Color::Yellow);
+const COMMENT: Style = Style::new().fg(Color::DarkGray);
+pub const PUNCTUATION: Style = Style::new().fg(Color::Gray);
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+/// Splits `source` into styled lines. This is a lexical pass only, so
+/// invalid input is still rendered, just without meaningful colours.
+pub fn highlight(source: &str, language: Language) -> Text<'static> {
+    let mut in_block_comment = false;
+    let lines: Vec<Line<'static>> = source
+        .lines()
+        .map(|line| match language {
+            Language::Json => Line::from(json_spans(line)),
+            Language::Protobuf => Line::from(proto_spans(line, &mut in_block_comment)),
+        })
+        .collect();
+    Text::from(lines)
+}
+
+/// Reads a string literal starting at `start`, returning its end index.
+fn string_end(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
+
+fn word_end(chars: &[char], start: usize) -> usize {
+    let mut i = start;
+    while i < chars.len() && (chars[i].is_alphanumeric() || "_.-+".contains(chars[i])) {
+        i += 1;
+    }
+    i.max(start + 1)
+}
+
+fn collect(chars: &[char], from: usize, to: usize) -> String {
+    chars[from..to].iter().collect()
+}
+
+fn json_spans(line: &str) -> Vec<Span<'static>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let (end, style) = if c == '"' {
+            let end = string_end(&chars, i);
+            // A string followed by a colon is an object key.
+            let is_key = chars[end..]
+                .iter()
+                .find(|c| !c.is_whitespace())
+                .is_some_and(|c| *c == ':');
+            (end, if is_key { KEY } else { STRING })
+        } else if c == '-' || c.is_ascii_digit() {
+            (word_end(&chars, i), NUMBER)
+        } else if c.is_alphabetic() {
+            (word_end(&chars, i), KEYWORD)
+        } else if c.is_whitespace() {
+            let mut end = i;
+            while end < chars.len() && chars[end].is_whitespace() {
+                end += 1;
+            }
+            (end, Style::default())
+        } else {
+            (i + 1, PUNCTUATION)
+        };
+        spans.push(Span::styled(collect(&chars, i, end), style));
+        i = end;
+    }
+    spans
+}
+
+fn proto_spans(line: &str, in_block_comment: &mut bool) -> Vec<Span<'static>> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if *in_block_comment {
+            let close = (i..chars.len().saturating_sub(1))
+                .find(|&j| chars[j] == '*' && chars[j + 1] == '/');
+            let end = close.map_or(chars.len(), |j| j + 2);
+            *in_block_comment = close.is_none();
+            spans.push(Span::styled(collect(&chars, i, end), COMMENT));
+            i = end;
+            continue;
+        }
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (end, style) = if c == '/' && next == Some('/') {
+            (chars.len(), COMMENT)
+        } else if c == '/' && next == Some('*') {
+            *in_block_comment = true;
+            spans.push(Span::styled("/*", COMMENT));
+            i += 2;
+            continue;
+        } else if c == '"' || c == '\'' {
+            (string_end(&chars, i), STRING)
+        } else if c.is_ascii_digit() {
+            (word_end(&chars, i), NUMBER)
+        } else if c.is_alphabetic() || c == '_' {
+            let end = word_end(&chars, i);
+            let word = collect(&chars, i, end);
+            let style = if PROTO_KEYWORDS.contains(&word.as_str()) {
+                KEYWORD
+            } else if PROTO_TYPES.contains(&word.as_str()) {
+                TYPE
+            } else {
+                Style::default()
+            };
+            (end, style)
+        } else if c.is_whitespace() {
+            let mut end = i;
+            while end < chars.len() && chars[end].is_whitespace() {
+                end += 1;
+            }
+            (end, Style::default())
+        } else {
+            (i + 1, PUNCTUATION)
+        };
+        spans.push(Span::styled(collect(&chars, i, end), style));
+        i = end;
+    }
+    spans
+}
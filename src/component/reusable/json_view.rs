@@ -0,0 +1,366 @@
+use crate::{
+    component::reusable::highlight::{KEY, KEYWORD, NUMBER, PUNCTUATION, STRING},
+    input::{handled, not_handled, InputHandled},
+    util::copy_to_clipboard,
+};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use serde_json::Value;
+use std::collections::HashSet;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Pretty-prints a JSON document with foldable objects and arrays. Text
+/// that isn't JSON is shown line by line instead.
+#[derive(Default)]
+pub struct JsonView {
+    text: String,
+    root: Option<Value>,
+    /// Paths of the collapsed objects and arrays.
+    collapsed: HashSet<String>,
+    rows: Vec<Row>,
+    pub cursor: usize,
+    copied: bool,
+}
+
+struct Row {
+    depth: usize,
+    path: String,
+    key: Option<String>,
+    kind: RowKind,
+    comma: bool,
+}
+
+enum RowKind {
+    /// The opening line of an object or array, with its number of entries.
+    Open {
+        brackets: (char, char),
+        len: usize,
+        collapsed: bool,
+    },
+    Close(char),
+    Scalar(Value),
+    Text(String),
+}
+
+impl JsonView {
+    /// Shows `text`, keeping the folding and cursor if it hasn't changed.
+    pub fn set_text(&mut self, text: &str) {
+        if self.text == text && !self.rows.is_empty() {
+            return;
+        }
+        self.text = text.to_string();
+        self.root = serde_json::from_str(text).ok();
+        self.collapsed.clear();
+        self.cursor = 0;
+        self.copied = false;
+        self.rebuild();
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// The JSON path of the node under the cursor, e.g. `$.items[2].id`.
+    pub fn cursor_path(&self) -> Option<&str> {
+        self.root.as_ref()?;
+        self.rows.get(self.cursor).map(|r| r.path.as_str())
+    }
+
+    fn rebuild(&mut self) {
+        self.rows.clear();
+        match self.root.take() {
+            Some(root) => {
+                flatten(
+                    &root,
+                    "$".to_string(),
+                    None,
+                    0,
+                    false,
+                    &self.collapsed,
+                    &mut self.rows,
+                );
+                self.root = Some(root);
+            }
+            None => self.rows.extend(self.text.lines().map(|line| Row {
+                depth: 0,
+                path: String::new(),
+                key: None,
+                kind: RowKind::Text(line.to_string()),
+                comma: false,
+            })),
+        }
+        self.cursor = self.cursor.min(self.rows.len().saturating_sub(1));
+    }
+
+    fn parent_row(&self) -> Option<usize> {
+        let depth = self.rows.get(self.cursor)?.depth;
+        (0..self.cursor).rev().find(|&i| {
+            self.rows[i].depth < depth && matches!(self.rows[i].kind, RowKind::Open { .. })
+        })
+    }
+}
+
+/// The path, object key and value of each entry in a container.
+type Children<'a> = Vec<(String, Option<String>, &'a Value)>;
+
+fn flatten(
+    value: &Value,
+    path: String,
+    key: Option<String>,
+    depth: usize,
+    comma: bool,
+    collapsed: &HashSet<String>,
+    rows: &mut Vec<Row>,
+) {
+    let (brackets, children): ((char, char), Children) = match value {
+        Value::Object(map) => (
+            ('{', '}'),
+            map.iter()
+                .map(|(k, v)| (child_path(&path, k), Some(k.clone()), v))
+                .collect(),
+        ),
+        Value::Array(items) => (
+            ('[', ']'),
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("{path}[{i}]"), None, v))
+                .collect(),
+        ),
+        scalar => {
+            rows.push(Row {
+                depth,
+                path,
+                key,
+                kind: RowKind::Scalar(scalar.clone()),
+                comma,
+            });
+            return;
+        }
+    };
+    let is_collapsed = collapsed.contains(&path);
+    // Empty containers and collapsed ones fit on a single row.
+    let single_row = is_collapsed || children.is_empty();
+    rows.push(Row {
+        depth,
+        path: path.clone(),
+        key,
+        kind: RowKind::Open {
+            brackets,
+            len: children.len(),
+            collapsed: is_collapsed,
+        },
+        comma: comma && single_row,
+    });
+    if single_row {
+        return;
+    }
+    let last = children.len() - 1;
+    for (i, (child, key, value)) in children.into_iter().enumerate() {
+        flatten(value, child, key, depth + 1, i < last, collapsed, rows);
+    }
+    rows.push(Row {
+        depth,
+        path,
+        key: None,
+        kind: RowKind::Close(brackets.1),
+        comma,
+    });
+}
+
+/// Object keys that aren't plain identifiers use the bracket notation.
+fn child_path(parent: &str, key: &str) -> String {
+    let identifier = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match identifier {
+        true => format!("{parent}.{key}"),
+        false => format!("{parent}[{}]", Value::from(key)),
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum JsonViewEvent {
+    MoveCursor(isize),
+    /// Collapses the node under the cursor, or moves to its parent.
+    Collapse,
+    Expand,
+    CopyPath,
+}
+
+// ==================
+// ==== HANDLERS ====
+// ==================
+
+pub fn on_event(state: &mut JsonView, event: JsonViewEvent) -> Option<JsonViewEvent> {
+    match event {
+        JsonViewEvent::MoveCursor(delta) => {
+            let last = state.rows.len().saturating_sub(1);
+            state.cursor = state.cursor.saturating_add_signed(delta).min(last);
+            state.copied = false;
+        }
+        JsonViewEvent::Collapse => match state.rows.get(state.cursor).map(|r| &r.kind) {
+            Some(RowKind::Open {
+                collapsed: false,
+                len,
+                ..
+            }) if *len > 0 => {
+                let path = state.rows[state.cursor].path.clone();
+                state.collapsed.insert(path);
+                state.rebuild();
+            }
+            _ => {
+                if let Some(parent) = state.parent_row() {
+                    state.cursor = parent;
+                }
+            }
+        },
+        JsonViewEvent::Expand => {
+            if let Some(row) = state.rows.get(state.cursor) {
+                if state.collapsed.remove(&row.path) {
+                    state.rebuild();
+                }
+            }
+        }
+        JsonViewEvent::CopyPath => {
+            if let Some(path) = state.cursor_path() {
+                state.copied = copy_to_clipboard(path).is_ok();
+            }
+        }
+    }
+    None
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+const PAGE: isize = 10;
+
+pub fn on_key(state: &JsonView, key: KeyEvent) -> InputHandled<JsonViewEvent> {
+    if state.rows.is_empty() {
+        return not_handled();
+    }
+    match key.code {
+        KeyCode::Char('j') => handled(JsonViewEvent::MoveCursor(1)),
+        KeyCode::Char('k') => handled(JsonViewEvent::MoveCursor(-1)),
+        KeyCode::PageDown => handled(JsonViewEvent::MoveCursor(PAGE)),
+        KeyCode::PageUp => handled(JsonViewEvent::MoveCursor(-PAGE)),
+        KeyCode::Left if state.is_json() => handled(JsonViewEvent::Collapse),
+        KeyCode::Right if state.is_json() => handled(JsonViewEvent::Expand),
+        KeyCode::Char('y') if state.is_json() => handled(JsonViewEvent::CopyPath),
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str = "j/k move, ←/→ fold, y copy path";
+
+/// Draws the document with the cursor's JSON path on the last line.
+pub fn draw(state: &JsonView, f: &mut Frame, area: Rect) {
+    let [body_area, path_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+    let height = body_area.height as usize;
+    let skip = (state.cursor + 1).saturating_sub(height);
+    let lines: Vec<Line> = state
+        .rows
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(height)
+        .map(|(i, row)| {
+            let line = Line::from(row_spans(row));
+            match i == state.cursor {
+                true => line.bg(Color::DarkGray),
+                false => line,
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), body_area);
+
+    let footer = match (state.cursor_path(), state.is_json()) {
+        (Some(path), _) => Line::from(vec![
+            Span::styled(path.to_string(), KEY),
+            Span::raw(match state.copied {
+                true => "  (copied)",
+                false => "",
+            }),
+            Span::styled(format!("  {HELP}"), Style::default().fg(Color::Gray)),
+        ]),
+        (None, false) => Line::from("Not valid JSON, shown as text").fg(Color::Gray),
+        (None, true) => Line::default(),
+    };
+    f.render_widget(Paragraph::new(footer), path_area);
+}
+
+fn row_spans(row: &Row) -> Vec<Span<'static>> {
+    let marker = match &row.kind {
+        RowKind::Open {
+            collapsed: true,
+            len,
+            ..
+        } if *len > 0 => "▸ ",
+        RowKind::Open { len, .. } if *len > 0 => "▾ ",
+        _ => "  ",
+    };
+    let mut spans = vec![Span::styled(
+        format!("{}{}", "  ".repeat(row.depth), marker),
+        PUNCTUATION,
+    )];
+    if let Some(key) = &row.key {
+        spans.push(Span::styled(Value::from(key.as_str()).to_string(), KEY));
+        spans.push(Span::styled(": ", PUNCTUATION));
+    }
+    match &row.kind {
+        RowKind::Open {
+            brackets: (open, close),
+            len,
+            collapsed,
+        } => {
+            if *len == 0 {
+                spans.push(Span::styled(format!("{open}{close}"), PUNCTUATION));
+            } else if *collapsed {
+                let noun = match open {
+                    '{' => "keys",
+                    _ => "items",
+                };
+                spans.push(Span::styled(format!("{open}…{close}"), PUNCTUATION));
+                spans.push(Span::styled(
+                    format!(" {len} {noun}"),
+                    Style::default().fg(Color::Gray),
+                ));
+            } else {
+                spans.push(Span::styled(open.to_string(), PUNCTUATION));
+            }
+        }
+        RowKind::Close(close) => spans.push(Span::styled(close.to_string(), PUNCTUATION)),
+        RowKind::Scalar(value) => {
+            let style = match value {
+                Value::String(_) => STRING,
+                Value::Number(_) => NUMBER,
+                _ => KEYWORD,
+            };
+            spans.push(Span::styled(value.to_string(), style));
+        }
+        RowKind::Text(text) => spans.push(Span::raw(text.clone())),
+    }
+    if row.comma {
+        spans.push(Span::styled(",", PUNCTUATION));
+    }
+    spans
+}
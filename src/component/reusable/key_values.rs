@@ -0,0 +1,302 @@
+use crate::{
+    component::reusable::text_field::{self, TextField, TextFieldEvent, TextFieldEventType},
+    input::{handled, handled_empty, not_handled, InputHandled},
+};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Pub/Sub's limits on message attributes.
+pub const MAX_ATTRIBUTES: usize = 100;
+pub const MAX_KEY_BYTES: usize = 256;
+pub const MAX_VALUE_BYTES: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    Key,
+    Value,
+}
+
+pub struct KeyValueRow {
+    pub key: TextField,
+    pub value: TextField,
+}
+
+/// An editable table of key/value pairs, such as message attributes. Each
+/// cell is a `TextField`, named `key-<id>` or `value-<id>` so its events
+/// can be routed back to it.
+#[derive(Default)]
+pub struct KeyValues {
+    pub rows: Vec<KeyValueRow>,
+    pub focused: Option<(usize, Column)>,
+    next_id: usize,
+}
+
+impl KeyValues {
+    fn cell(&self, row: usize, column: Column) -> Option<&TextField> {
+        self.rows.get(row).map(|r| match column {
+            Column::Key => &r.key,
+            Column::Value => &r.value,
+        })
+    }
+
+    fn cell_by_name(&mut self, name: &str) -> Option<&mut TextField> {
+        self.rows.iter_mut().find_map(|r| {
+            if r.key.name == name {
+                Some(&mut r.key)
+            } else if r.value.name == name {
+                Some(&mut r.value)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn focused_cell(&self) -> Option<&TextField> {
+        self.focused
+            .and_then(|(row, column)| self.cell(row, column))
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.focused_cell().is_some_and(|c| c.is_editing)
+    }
+
+    fn new_row(&mut self) -> KeyValueRow {
+        self.next_id += 1;
+        KeyValueRow {
+            key: TextField::new(&format!("key-{}", self.next_id), "Key"),
+            value: TextField::new(&format!("value-{}", self.next_id), "Value"),
+        }
+    }
+
+    /// Problems with each row, by row index. Rows left entirely empty are
+    /// ignored.
+    pub fn problems(&self) -> Vec<(usize, String)> {
+        let mut problems = Vec::new();
+        let mut seen = HashMap::new();
+        for (i, row) in self.rows.iter().enumerate() {
+            let (key, value) = (&row.key.value, &row.value.value);
+            if key.is_empty() && value.is_empty() {
+                continue;
+            }
+            if key.is_empty() {
+                problems.push((i, "Attribute keys can't be empty".to_string()));
+            } else if key.len() > MAX_KEY_BYTES {
+                problems.push((i, format!("'{key}' is over {MAX_KEY_BYTES} bytes")));
+            } else if key.to_ascii_lowercase().starts_with("goog") {
+                problems.push((i, format!("'{key}' uses the reserved goog prefix")));
+            } else if let Some(first) = seen.insert(key, i) {
+                problems.push((i, format!("'{key}' is already set on row {}", first + 1)));
+            }
+            if value.len() > MAX_VALUE_BYTES {
+                problems.push((
+                    i,
+                    format!("The value of '{key}' is over {MAX_VALUE_BYTES} bytes"),
+                ));
+            }
+        }
+        problems
+    }
+
+    /// The pairs to publish, or the first problem found.
+    pub fn to_map(&self) -> Result<HashMap<String, String>, String> {
+        if let Some((row, problem)) = self.problems().into_iter().next() {
+            return Err(format!("Attribute row {}: {}", row + 1, problem));
+        }
+        let pairs: HashMap<String, String> = self
+            .rows
+            .iter()
+            .filter(|r| !r.key.value.is_empty())
+            .map(|r| (r.key.value.clone(), r.value.value.clone()))
+            .collect();
+        if pairs.len() > MAX_ATTRIBUTES {
+            return Err(format!(
+                "A message can have at most {MAX_ATTRIBUTES} attributes"
+            ));
+        }
+        Ok(pairs)
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum KeyValuesEvent {
+    Field(TextFieldEvent),
+    Focus(Option<(usize, Column)>),
+    AddRow,
+    RemoveRow(usize),
+}
+
+impl From<TextFieldEvent> for KeyValuesEvent {
+    fn from(event: TextFieldEvent) -> Self {
+        KeyValuesEvent::Field(event)
+    }
+}
+
+// ==================
+// ==== HANDLERS ====
+// ==================
+
+pub fn on_event(state: &mut KeyValues, event: KeyValuesEvent) -> Option<KeyValuesEvent> {
+    match event {
+        KeyValuesEvent::Field(e) => {
+            let cell = state.cell_by_name(&e.name)?;
+            text_field::on_event(cell, e.event_type).map(KeyValuesEvent::from)
+        }
+        KeyValuesEvent::Focus(focused) => {
+            state.focused = focused;
+            None
+        }
+        KeyValuesEvent::AddRow => {
+            if state.rows.len() >= MAX_ATTRIBUTES {
+                return None;
+            }
+            let row = state.new_row();
+            let name = row.key.name.clone();
+            state.rows.push(row);
+            state.focused = Some((state.rows.len() - 1, Column::Key));
+            Some(TextFieldEvent::new(name, TextFieldEventType::StartEditing).into())
+        }
+        KeyValuesEvent::RemoveRow(row) => {
+            if row < state.rows.len() {
+                state.rows.remove(row);
+            }
+            state.focused = match state.rows.is_empty() {
+                true => None,
+                false => state.focused.map(|(r, c)| (r.min(state.rows.len() - 1), c)),
+            };
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &KeyValues, key: KeyEvent) -> InputHandled<KeyValuesEvent> {
+    if let Some(cell) = state.focused_cell() {
+        let cell_handled = text_field::on_key(cell, key).map(KeyValuesEvent::from);
+        if cell_handled.is_handled() || cell.is_editing {
+            return cell_handled;
+        }
+    }
+    let count = state.rows.len();
+    let focus = |row, column| handled(KeyValuesEvent::Focus(Some((row, column))));
+    match (key.code, state.focused) {
+        (KeyCode::Char('a') | KeyCode::Insert, _) => handled(KeyValuesEvent::AddRow),
+        (KeyCode::Char('d') | KeyCode::Delete, Some((row, _))) => {
+            handled(KeyValuesEvent::RemoveRow(row))
+        }
+        (KeyCode::Up | KeyCode::Down, _) if count == 0 => handled_empty(),
+        (KeyCode::Up, Some((row, column))) => focus(row.saturating_sub(1), column),
+        (KeyCode::Down, Some((row, column))) => focus((row + 1).min(count - 1), column),
+        (KeyCode::Up | KeyCode::Down, None) => focus(0, Column::Key),
+        (KeyCode::Left, Some((row, _))) => focus(row, Column::Key),
+        (KeyCode::Right, Some((row, _))) => focus(row, Column::Value),
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const HELP: &str = "a add, d remove, ↑/↓/←/→ move, Spacebar to edit";
+
+pub fn draw(state: &KeyValues, label: &str, is_focused: bool, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .title(format!("{label} ({}/{MAX_ATTRIBUTES})", state.rows.len()))
+        .borders(Borders::ALL)
+        .border_style(match is_focused {
+            true => Style::default().bold(),
+            false => Style::default(),
+        });
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let [rows_area, footer_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+    let problems = state.problems();
+    let key_width = rows_area.width * 2 / 5;
+    let height = rows_area.height as usize;
+    let focused_row = state.focused.map_or(0, |(row, _)| row);
+    let skip = (focused_row + 1).saturating_sub(height);
+    let mut lines = Vec::new();
+    for (i, row) in state.rows.iter().enumerate().skip(skip).take(height) {
+        let invalid = problems.iter().any(|(r, _)| *r == i);
+        let cell = |field: &TextField, column: Column, width: u16| {
+            let text = match field.is_editing {
+                true => &field.input,
+                false => &field.value,
+            };
+            let mut style = match invalid {
+                true => Style::default().fg(Color::LightRed),
+                false => Style::default(),
+            };
+            if is_focused && state.focused == Some((i, column)) {
+                style = match field.is_editing {
+                    true => style.bold().yellow(),
+                    false => style.bold().green().reversed(),
+                };
+            }
+            let text: String = text
+                .chars()
+                .take(width.saturating_sub(1) as usize)
+                .collect();
+            Span::styled(format!("{text:<width$}", width = width as usize), style)
+        };
+        lines.push(Line::from(vec![
+            cell(&row.key, Column::Key, key_width),
+            Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+            cell(
+                &row.value,
+                Column::Value,
+                rows_area.width.saturating_sub(key_width + 2),
+            ),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from("No attributes, press a to add one").fg(Color::Gray));
+    }
+    f.render_widget(Paragraph::new(lines), rows_area);
+
+    let footer = match problems.first() {
+        Some((row, problem)) => {
+            Line::from(format!("Row {}: {}", row + 1, problem)).fg(Color::LightRed)
+        }
+        None if state.rows.len() >= MAX_ATTRIBUTES => {
+            Line::from("Attribute limit reached").fg(Color::LightRed)
+        }
+        None => Line::from(HELP).fg(Color::Gray),
+    };
+    f.render_widget(Paragraph::new(footer), footer_area);
+
+    if let Some((row, column)) = state.focused.filter(|_| is_focused) {
+        let Some(field) = state.cell(row, column).filter(|c| c.is_editing) else {
+            return;
+        };
+        let x = match column {
+            Column::Key => rows_area.x,
+            Column::Value => rows_area.x + key_width + 2,
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        f.set_cursor_position(Position::new(
+            x + field.character_index as u16,
+            rows_area.y + (row - skip) as u16,
+        ));
+    }
+}
@@ -1,2 +1,7 @@
 pub mod choices;
+pub mod form;
+pub mod hex_view;
+pub mod highlight;
+pub mod json_view;
+pub mod key_values;
 pub mod text_field;
@@ -1,3 +1,5 @@
+#![allow(clippy::legacy_numeric_constants)]
+
 use std::usize;
 
 use crate::{
@@ -279,6 +281,7 @@ fn on_delete_right(state: &mut TextField, number: usize) -> Option<TextFieldEven
 // ==== INPUT ====
 // ===============
 
+#[allow(clippy::useless_conversion)]
 pub fn on_key(state: &TextField, key: KeyEvent) -> InputHandled<TextFieldEvent> {
     match state.is_editing {
         true => match key.code {
@@ -0,0 +1,679 @@
+use crate::{
+    app::App,
+    component::{
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            highlight::{highlight, Language},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, not_handled, InputHandled},
+    route::{select_route, Route},
+    util::{format_timestamp, short_name, validate_resource_id},
+};
+use google_cloud_googleapis::pubsub::v1::{schema::Type, Schema};
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Enter, Esc, PageDown, PageUp, Up},
+        KeyEvent,
+    },
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, List, ListState, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::time::SystemTime;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone)]
+pub struct SchemaInfo {
+    pub name: String,
+    pub kind: Type,
+    pub definition: String,
+    pub revision_id: String,
+    pub revision_create_time: Option<SystemTime>,
+}
+
+impl From<Schema> for SchemaInfo {
+    fn from(schema: Schema) -> Self {
+        Self {
+            name: schema.name,
+            kind: Type::from_i32(schema.r#type).unwrap_or(Type::Unspecified),
+            definition: schema.definition,
+            revision_id: schema.revision_id,
+            revision_create_time: schema
+                .revision_create_time
+                .and_then(|t| SystemTime::try_from(t).ok()),
+        }
+    }
+}
+
+impl SchemaInfo {
+    pub fn language(&self) -> Language {
+        match self.kind {
+            Type::ProtocolBuffer => Language::Protobuf,
+            _ => Language::Json,
+        }
+    }
+
+    fn short_revision(&self) -> &str {
+        &self.revision_id[..self.revision_id.len().min(8)]
+    }
+}
+
+/// A schema definition to create, or to commit as a new revision.
+#[derive(Debug, Clone)]
+pub struct NewSchema {
+    pub id: String,
+    pub kind: Type,
+    pub definition: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorMode {
+    Create,
+    /// Committing a new revision of the named schema.
+    Commit(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaPane {
+    Definition,
+    Diff,
+}
+
+pub struct Schemas {
+    pub all: Vec<SchemaInfo>,
+    pub selected: Option<usize>,
+    /// Revisions of the selected schema, newest first.
+    pub revisions: Vec<SchemaInfo>,
+    pub revision_selected: Option<usize>,
+    /// The revision the selected one is diffed against.
+    pub diff_base: Option<usize>,
+    pub pane: SchemaPane,
+    /// The first line shown in the definition or diff pane.
+    pub scroll: usize,
+    pub editor: Option<EditorMode>,
+    pub form: Form,
+    pub error: Option<String>,
+    /// The revision ID a rollback is waiting to be confirmed for.
+    pub rolling_back: Option<String>,
+    /// The selected revision diffed against `diff_base`, derived by
+    /// `refresh_diff` so drawing and scrolling don't diff every time.
+    diff: Vec<(Change, String)>,
+}
+
+impl Default for Schemas {
+    fn default() -> Self {
+        Self {
+            all: Vec::new(),
+            selected: None,
+            revisions: Vec::new(),
+            revision_selected: None,
+            diff_base: None,
+            pane: SchemaPane::Definition,
+            scroll: 0,
+            editor: None,
+            form: Form::new(vec![
+                Field::Text(TextField::new("id", "Schema ID")),
+                Field::Choices(Choices::new(
+                    "type",
+                    "Type",
+                    vec![
+                        Choice {
+                            label: "Avro".into(),
+                            value: "AVRO".into(),
+                        },
+                        Choice {
+                            label: "Protocol Buffer".into(),
+                            value: "PROTOCOL_BUFFER".into(),
+                        },
+                    ],
+                )),
+                Field::Text(TextField::new(
+                    "definition",
+                    "Definition (inline, or @path/to/file)",
+                )),
+            ]),
+            error: None,
+            rolling_back: None,
+            diff: Vec::new(),
+        }
+    }
+}
+
+impl Schemas {
+    /// Keeps the selection on the same schema name across refreshes.
+    pub fn set_schemas(&mut self, schemas: Vec<SchemaInfo>) {
+        let selected_name = self.selected().map(|s| s.name.clone());
+        self.all = schemas;
+        self.selected = match selected_name {
+            Some(name) => self.all.iter().position(|s| s.name == name),
+            None => None,
+        }
+        .or(match self.all.is_empty() {
+            true => None,
+            false => Some(0),
+        });
+        self.refresh_diff();
+    }
+
+    pub fn set_revisions(&mut self, name: &str, revisions: Vec<SchemaInfo>) {
+        if self.selected().map(|s| s.name.as_str()) != Some(name) {
+            return;
+        }
+        self.revisions = revisions;
+        self.revision_selected = match self.revisions.is_empty() {
+            true => None,
+            false => Some(0),
+        };
+        self.diff_base = None;
+        self.pane = SchemaPane::Definition;
+        self.scroll = 0;
+        self.refresh_diff();
+    }
+
+    pub fn selected(&self) -> Option<&SchemaInfo> {
+        self.selected.and_then(|i| self.all.get(i))
+    }
+
+    pub fn selected_revision(&self) -> Option<&SchemaInfo> {
+        self.revision_selected
+            .and_then(|i| self.revisions.get(i))
+            .or(self.selected())
+    }
+
+    /// Recomputes the diff. Call whenever the revisions, the selected one or
+    /// the diff base change.
+    fn refresh_diff(&mut self) {
+        let base = self.diff_base.and_then(|i| self.revisions.get(i));
+        self.diff = match (base, self.selected_revision()) {
+            (Some(base), Some(other)) => diff_lines(&base.definition, &other.definition)
+                .into_iter()
+                .map(|(change, line)| (change, line.to_string()))
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+
+    /// How many lines the definition or diff pane has.
+    fn pane_lines(&self) -> usize {
+        let base = self.diff_base.and_then(|i| self.revisions.get(i));
+        match (self.pane, base, self.selected_revision()) {
+            (SchemaPane::Diff, Some(_), Some(_)) => self.diff.len(),
+            (_, _, Some(revision)) => revision.definition.lines().count(),
+            _ => 0,
+        }
+    }
+
+    fn new_schema(&self) -> Result<NewSchema, String> {
+        let id = match &self.editor {
+            Some(EditorMode::Commit(name)) => name.clone(),
+            _ => {
+                let id = self.form.get("id").trim().to_string();
+                validate_resource_id(&id)?;
+                id
+            }
+        };
+        let kind = Type::from_str_name(self.form.get("type")).ok_or("Choose a schema type")?;
+        let definition = self.form.get("definition").trim();
+        let definition = match definition.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path.trim())
+                .map_err(|e| format!("Failed to read {}: {}", path.trim(), e))?,
+            None => definition.to_string(),
+        };
+        if definition.trim().is_empty() {
+            return Err("The definition is empty".to_string());
+        }
+        Ok(NewSchema {
+            id,
+            kind,
+            definition,
+        })
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum SchemasEvent {
+    Select(Option<usize>),
+    /// Selects a schema by its full name and switches to the Schemas route.
+    Show(String),
+    SelectRevision(Option<usize>),
+    MarkDiffBase,
+    TogglePane,
+    /// Scrolls the definition or diff pane by a number of lines.
+    Scroll(isize),
+    /// Asks to confirm rolling back to a revision ID, or cancels.
+    ConfirmRollback(Option<String>),
+    OpenEditor(EditorMode),
+    CloseEditor,
+    Form(FormEvent),
+    SubmitEditor,
+}
+
+impl From<SchemasEvent> for AppEvent {
+    fn from(event: SchemasEvent) -> Self {
+        AppEvent::Schemas(event)
+    }
+}
+
+impl From<FormEvent> for SchemasEvent {
+    fn from(event: FormEvent) -> Self {
+        SchemasEvent::Form(event)
+    }
+}
+
+pub fn load_revisions(state: &Schemas) -> Option<AppEvent> {
+    state
+        .selected()
+        .map(|s| PubsubEvent::GetSchemaRevisions(s.name.clone()).into())
+}
+
+pub fn on_event(state: &mut Schemas, event: SchemasEvent) -> Option<AppEvent> {
+    match event {
+        SchemasEvent::Select(idx) => {
+            state.selected = idx;
+            state.revisions.clear();
+            state.revision_selected = None;
+            state.diff_base = None;
+            state.pane = SchemaPane::Definition;
+            state.scroll = 0;
+            state.refresh_diff();
+            load_revisions(state)
+        }
+        SchemasEvent::Show(name) => {
+            if let Some(idx) = state.all.iter().position(|s| s.name == name) {
+                state.selected = Some(idx);
+                state.revisions.clear();
+                state.revision_selected = None;
+                state.refresh_diff();
+            }
+            // The route loads the revisions once it is shown.
+            Some(select_route(Route::Schemas))
+        }
+        SchemasEvent::SelectRevision(idx) => {
+            state.revision_selected = idx;
+            state.scroll = 0;
+            state.refresh_diff();
+            None
+        }
+        SchemasEvent::MarkDiffBase => {
+            state.diff_base = state.revision_selected;
+            state.refresh_diff();
+            None
+        }
+        SchemasEvent::TogglePane => {
+            state.pane = match state.pane {
+                SchemaPane::Definition if state.diff_base.is_some() => SchemaPane::Diff,
+                _ => SchemaPane::Definition,
+            };
+            state.scroll = 0;
+            None
+        }
+        SchemasEvent::Scroll(lines) => {
+            let last = state.pane_lines().saturating_sub(1);
+            state.scroll = state.scroll.saturating_add_signed(lines).min(last);
+            None
+        }
+        SchemasEvent::ConfirmRollback(revision_id) => {
+            state.rolling_back = revision_id;
+            None
+        }
+        SchemasEvent::OpenEditor(mode) => {
+            let (id, kind) = match &mode {
+                EditorMode::Create => (String::new(), "AVRO".to_string()),
+                EditorMode::Commit(name) => (
+                    short_name(name).to_string(),
+                    state
+                        .selected()
+                        .map(|s| s.kind.as_str_name().to_string())
+                        .unwrap_or_default(),
+                ),
+            };
+            state.form.set("id", id);
+            state.form.set("type", kind);
+            state.form.set("definition", String::new());
+            state.error = None;
+            let focus = match mode {
+                EditorMode::Create => "id",
+                EditorMode::Commit(_) => "definition",
+            };
+            state.editor = Some(mode);
+            Some(SchemasEvent::Form(FormEvent::Focus(Some(focus.into()))).into())
+        }
+        SchemasEvent::CloseEditor => {
+            state.editor = None;
+            state.form.focused = None;
+            None
+        }
+        SchemasEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(SchemasEvent::from)
+            .map(AppEvent::from),
+        SchemasEvent::SubmitEditor => match state.new_schema() {
+            Ok(schema) => {
+                let event = match state.editor.take() {
+                    Some(EditorMode::Commit(name)) => PubsubEvent::CommitSchema(name, schema),
+                    _ => PubsubEvent::CreateSchema(schema),
+                };
+                state.form.focused = None;
+                Some(event.into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+const PAGE: isize = 10;
+
+pub fn on_key(pubsub: &Pubsub, key: KeyEvent) -> InputHandled<AppEvent> {
+    let state = &pubsub.schemas;
+    if state.editor.is_some() {
+        let form_handled = form::on_key(&state.form, key)
+            .map(SchemasEvent::from)
+            .map(AppEvent::from);
+        if form_handled.is_handled() {
+            return form_handled;
+        }
+        return match key.code {
+            Enter => handled(SchemasEvent::SubmitEditor.into()),
+            Esc => handled(SchemasEvent::CloseEditor.into()),
+            _ => handled_empty(),
+        };
+    }
+    if let (Some(schema), Some(revision_id)) = (state.selected(), &state.rolling_back) {
+        return match key.code {
+            Char('y') => handled(
+                PubsubEvent::RollbackSchema(schema.name.clone(), revision_id.clone()).into(),
+            ),
+            Char('n') | Esc => handled(SchemasEvent::ConfirmRollback(None).into()),
+            _ => handled_empty(),
+        };
+    }
+
+    let count = state.all.len();
+    let revisions = state.revisions.len();
+    match key.code {
+        Up | Down if count == 0 => not_handled(),
+        Up => {
+            let idx = state
+                .selected
+                .map_or(count - 1, |i| (i + count - 1) % count);
+            handled(SchemasEvent::Select(Some(idx)).into())
+        }
+        Down => {
+            let idx = state.selected.map_or(0, |i| (i + 1) % count);
+            handled(SchemasEvent::Select(Some(idx)).into())
+        }
+        Char('j' | 'k') if revisions == 0 => not_handled(),
+        Char('k') => {
+            let idx = state
+                .revision_selected
+                .map_or(revisions - 1, |i| (i + revisions - 1) % revisions);
+            handled(SchemasEvent::SelectRevision(Some(idx)).into())
+        }
+        Char('j') => {
+            let idx = state.revision_selected.map_or(0, |i| (i + 1) % revisions);
+            handled(SchemasEvent::SelectRevision(Some(idx)).into())
+        }
+        Char('m') if state.revision_selected.is_some() => {
+            handled(SchemasEvent::MarkDiffBase.into())
+        }
+        Char('v') => handled(SchemasEvent::TogglePane.into()),
+        Char('r') => handled(PubsubEvent::GetSchemas.into()),
+        Char('c') => handled(SchemasEvent::OpenEditor(EditorMode::Create).into()),
+        Char('n') => match state.selected() {
+            Some(schema) => {
+                handled(SchemasEvent::OpenEditor(EditorMode::Commit(schema.name.clone())).into())
+            }
+            None => not_handled(),
+        },
+        Char('b') => match state.revision_selected {
+            // Rolling back to the newest revision would be a no-op.
+            Some(idx) if idx > 0 => handled(
+                SchemasEvent::ConfirmRollback(Some(state.revisions[idx].revision_id.clone()))
+                    .into(),
+            ),
+            _ => not_handled(),
+        },
+        PageDown => handled(SchemasEvent::Scroll(PAGE).into()),
+        PageUp => handled(SchemasEvent::Scroll(-PAGE).into()),
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const TITLE: &str = "Schemas (↑/↓ select, j/k revision, m mark for diff, v toggle diff, \
+     PgUp/PgDn scroll, c create, n new revision, b roll back, r refresh)";
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to save, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &App, f: &mut Frame, area: Rect) {
+    let schemas = &state.pubsub.schemas;
+    let block = Block::default()
+        .title(TITLE)
+        .fg(Color::LightYellow)
+        .bg(Color::Black)
+        .borders(Borders::ALL);
+    f.render_widget(block, area);
+
+    let [left_area, right_area] = Layout::default()
+        .margin(1)
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .areas(area);
+    let [list_area, revisions_area] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(left_area);
+
+    let list = List::new(
+        schemas
+            .all
+            .iter()
+            .map(|s| format!("{} ({})", short_name(&s.name), type_label(s.kind))),
+    )
+    .highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(Block::default().borders(Borders::ALL));
+    let mut list_state = ListState::default().with_selected(schemas.selected);
+    f.render_stateful_widget(list, list_area, &mut list_state);
+
+    draw_revisions(schemas, f, revisions_area);
+    match (&schemas.editor, schemas.pane) {
+        (Some(mode), _) => draw_editor(schemas, mode, f, right_area),
+        (None, SchemaPane::Diff) => draw_diff(schemas, f, right_area),
+        (None, SchemaPane::Definition) => draw_definition(schemas, f, right_area),
+    }
+}
+
+fn type_label(kind: Type) -> &'static str {
+    match kind {
+        Type::Avro => "Avro",
+        Type::ProtocolBuffer => "Protobuf",
+        Type::Unspecified => "?",
+    }
+}
+
+fn draw_revisions(schemas: &Schemas, f: &mut Frame, area: Rect) {
+    let header = Row::new(vec!["", "Revision", "Created"])
+        .style(Style::default().bold().fg(Color::LightCyan));
+    let rows = schemas.revisions.iter().enumerate().map(|(i, r)| {
+        let marker = match schemas.diff_base == Some(i) {
+            true => "◆",
+            false => "",
+        };
+        Row::new(vec![
+            marker.to_string(),
+            r.short_revision().to_string(),
+            r.revision_create_time
+                .map(format_timestamp)
+                .unwrap_or_default(),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(9),
+            Constraint::Min(0),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(Block::default().title("Revisions").borders(Borders::ALL));
+    let mut table_state = TableState::default().with_selected(schemas.revision_selected);
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn draw_definition(schemas: &Schemas, f: &mut Frame, area: Rect) {
+    let Some(revision) = schemas.selected_revision() else {
+        let empty = Paragraph::new("Select a schema to see its definition")
+            .fg(Color::Gray)
+            .block(Block::default().title("Definition").borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+    let title = format!(
+        "{} @ {}",
+        short_name(&revision.name),
+        revision.short_revision()
+    );
+    let area = draw_rollback_prompt(schemas, f, area);
+    let paragraph = Paragraph::new(highlight(&revision.definition, revision.language()))
+        .scroll((schemas.scroll.min(u16::MAX as usize) as u16, 0))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
+
+/// Asks to confirm a rollback above the pane, returning the area left.
+fn draw_rollback_prompt(schemas: &Schemas, f: &mut Frame, area: Rect) -> Rect {
+    let Some(revision_id) = &schemas.rolling_back else {
+        return area;
+    };
+    let [prompt_area, rest] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+    let revision = &revision_id[..revision_id.len().min(8)];
+    let prompt = Paragraph::new(format!(
+        "Roll back to revision {revision}? It is committed again as the newest revision. (y/n)"
+    ))
+    .fg(Color::LightRed);
+    f.render_widget(prompt, prompt_area);
+    rest
+}
+
+fn draw_diff(schemas: &Schemas, f: &mut Frame, area: Rect) {
+    let (Some(base), Some(other)) = (
+        schemas.diff_base.and_then(|i| schemas.revisions.get(i)),
+        schemas.selected_revision(),
+    ) else {
+        return draw_definition(schemas, f, area);
+    };
+    let lines: Vec<Line> = schemas
+        .diff
+        .iter()
+        .map(|(change, line)| match change {
+            Change::Same => Line::raw(format!("  {line}")).fg(Color::Gray),
+            Change::Removed => Line::raw(format!("- {line}")).fg(Color::LightRed),
+            Change::Added => Line::raw(format!("+ {line}")).fg(Color::LightGreen),
+        })
+        .collect();
+    let title = format!(
+        "Diff {} → {}",
+        base.short_revision(),
+        other.short_revision()
+    );
+    let area = draw_rollback_prompt(schemas, f, area);
+    let paragraph = Paragraph::new(Text::from(lines))
+        .scroll((schemas.scroll.min(u16::MAX as usize) as u16, 0))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
+
+fn draw_editor(schemas: &Schemas, mode: &EditorMode, f: &mut Frame, area: Rect) {
+    let title = match mode {
+        EditorMode::Create => "Create schema".to_string(),
+        EditorMode::Commit(name) => format!("New revision of {}", short_name(name)),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let mut help = vec![Line::from(match schemas.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &schemas.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&schemas.form, f, fields_area);
+}
+
+// ==============
+// ==== DIFF ====
+// ==============
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Line diff via longest common subsequence. The quadratic table is built
+/// once per revision pair, when `Schemas::refresh_diff` caches the result.
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(Change, &'a str)> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = match old[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            out.push((Change::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push((Change::Removed, old[i]));
+            i += 1;
+        } else {
+            out.push((Change::Added, new[j]));
+            j += 1;
+        }
+    }
+    out.extend(old[i..].iter().map(|l| (Change::Removed, *l)));
+    out.extend(new[j..].iter().map(|l| (Change::Added, *l)));
+    out
+}
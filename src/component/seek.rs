@@ -0,0 +1,215 @@
+use crate::{
+    component::{
+        pubsub::PubsubEvent,
+        reusable::{
+            choices::{Choice, Choices},
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+        snapshots::SnapshotInfo,
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, InputHandled},
+    util::{parse_time, short_name},
+};
+use google_cloud_pubsub::subscription::SeekTo;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Enter, Esc},
+        KeyEvent,
+    },
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Text},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::time::SystemTime;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Seeks a subscription either to a point in time or to a snapshot of the
+/// same topic.
+pub struct Seek {
+    pub subscription: Option<String>,
+    pub topic: String,
+    pub form: Form,
+    pub error: Option<String>,
+}
+
+impl Default for Seek {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            topic: String::new(),
+            form: seek_form(Vec::new()),
+            error: None,
+        }
+    }
+}
+
+fn seek_form(snapshots: Vec<Choice>) -> Form {
+    let mut choices = vec![Choice {
+        label: "None".into(),
+        value: String::new(),
+    }];
+    choices.extend(snapshots);
+    Form::new(vec![
+        Field::Text(TextField::new(
+            "time",
+            "Time (RFC3339, or relative such as -15m)",
+        )),
+        Field::Choices(Choices::new("snapshot", "Snapshot", choices)),
+    ])
+}
+
+impl Seek {
+    pub fn is_open(&self) -> bool {
+        self.subscription.is_some()
+    }
+
+    fn target(&self) -> Result<(String, SeekTo), String> {
+        let subscription = self
+            .subscription
+            .clone()
+            .ok_or("No subscription selected")?;
+        let time = self.form.get("time").trim();
+        let snapshot = self.form.get("snapshot");
+        let to = match (time.is_empty(), snapshot.is_empty()) {
+            (true, true) => return Err("Enter a time or choose a snapshot".to_string()),
+            (false, false) => return Err("Enter a time or a snapshot, not both".to_string()),
+            (false, true) => SeekTo::Timestamp(parse_time(time, SystemTime::now())?),
+            (true, false) => SeekTo::Snapshot(snapshot.clone()),
+        };
+        Ok((subscription, to))
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum SeekEvent {
+    /// Subscription and the topic it is attached to.
+    Open(String, String),
+    Close,
+    SetSnapshots(Vec<SnapshotInfo>),
+    Form(FormEvent),
+    Submit,
+}
+
+impl From<SeekEvent> for AppEvent {
+    fn from(event: SeekEvent) -> Self {
+        AppEvent::Seek(event)
+    }
+}
+
+impl From<FormEvent> for SeekEvent {
+    fn from(event: FormEvent) -> Self {
+        SeekEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Seek, event: SeekEvent) -> Option<AppEvent> {
+    match event {
+        SeekEvent::Open(subscription, topic) => {
+            *state = Seek {
+                subscription: Some(subscription),
+                topic,
+                ..Default::default()
+            };
+            state.form.focused = Some("time".into());
+            Some(PubsubEvent::GetSnapshots.into())
+        }
+        SeekEvent::Close => {
+            state.subscription = None;
+            None
+        }
+        SeekEvent::SetSnapshots(snapshots) => {
+            // Only snapshots of the subscription's own topic can be seeked to.
+            let choices = snapshots
+                .into_iter()
+                .filter(|s| s.topic == state.topic)
+                .map(|s| Choice {
+                    label: short_name(&s.name).to_string(),
+                    value: s.name,
+                })
+                .collect();
+            let time = state.form.get("time").clone();
+            let focused = state.form.focused.take();
+            state.form = seek_form(choices);
+            state.form.set("time", time);
+            state.form.focused = focused;
+            None
+        }
+        SeekEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(SeekEvent::from)
+            .map(AppEvent::from),
+        SeekEvent::Submit => match state.target() {
+            Ok((subscription, to)) => {
+                state.subscription = None;
+                state.error = None;
+                Some(PubsubEvent::Seek(subscription, to).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Seek, key: KeyEvent) -> InputHandled<AppEvent> {
+    let form_handled = form::on_key(&state.form, key)
+        .map(SeekEvent::from)
+        .map(AppEvent::from);
+    if form_handled.is_handled() {
+        return form_handled;
+    }
+    match key.code {
+        Enter => handled(SeekEvent::Submit.into()),
+        Esc => handled(SeekEvent::Close.into()),
+        _ => handled_empty(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to seek, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &Seek, f: &mut Frame, area: Rect) {
+    let subscription = state
+        .subscription
+        .as_deref()
+        .map(short_name)
+        .unwrap_or_default();
+    let block = Block::default()
+        .title(format!("Seek {subscription}"))
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let mut help = vec![Line::from(match state.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &state.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&state.form, f, fields_area);
+}
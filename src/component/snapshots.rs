@@ -0,0 +1,391 @@
+use crate::{
+    app::App,
+    component::{
+        pubsub::{Pubsub, PubsubEvent},
+        reusable::{
+            form::{self, Field, Form, FormEvent},
+            text_field::TextField,
+        },
+    },
+    event::AppEvent,
+    input::{handled, handled_empty, not_handled, InputHandled},
+    util::{format_timestamp, parse_key_values, short_name, validate_labels, validate_resource_id},
+};
+use google_cloud_googleapis::pubsub::v1::Snapshot;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Enter, Esc, Up},
+        KeyEvent,
+    },
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::SystemTime,
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub topic: String,
+    pub expire_time: Option<SystemTime>,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl From<Snapshot> for SnapshotInfo {
+    fn from(snapshot: Snapshot) -> Self {
+        Self {
+            name: snapshot.name,
+            topic: snapshot.topic,
+            expire_time: snapshot
+                .expire_time
+                .and_then(|t| SystemTime::try_from(t).ok()),
+            labels: snapshot.labels.into_iter().collect(),
+        }
+    }
+}
+
+/// A snapshot to be taken of a subscription's acknowledgment state.
+#[derive(Debug, Clone)]
+pub struct NewSnapshot {
+    pub id: String,
+    pub subscription: String,
+    pub labels: HashMap<String, String>,
+}
+
+pub struct Snapshots {
+    pub all: Vec<SnapshotInfo>,
+    pub selected: Option<usize>,
+    /// The API doesn't say which subscription a snapshot was taken from, so
+    /// this only knows about snapshots created in this session.
+    pub sources: HashMap<String, String>,
+    pub creating: bool,
+    pub form: Form,
+    pub error: Option<String>,
+    pub deleting: Option<String>,
+}
+
+impl Default for Snapshots {
+    fn default() -> Self {
+        Self {
+            all: Vec::new(),
+            selected: None,
+            sources: HashMap::new(),
+            creating: false,
+            form: Form::new(vec![
+                Field::Text(TextField::new("id", "Snapshot ID")),
+                Field::Text(TextField::new("subscription", "Source subscription")),
+                Field::Text(TextField::new(
+                    "labels",
+                    "Labels (key=value, comma separated)",
+                )),
+            ]),
+            error: None,
+            deleting: None,
+        }
+    }
+}
+
+impl Snapshots {
+    pub fn set_snapshots(&mut self, snapshots: Vec<SnapshotInfo>) {
+        self.all = snapshots;
+        self.selected = match self.all.is_empty() {
+            true => None,
+            false => Some(self.selected.unwrap_or(0).min(self.all.len() - 1)),
+        };
+    }
+
+    pub fn selected(&self) -> Option<&SnapshotInfo> {
+        self.selected.and_then(|i| self.all.get(i))
+    }
+
+    fn new_snapshot(&self) -> Result<NewSnapshot, String> {
+        let id = self.form.get("id").trim().to_string();
+        validate_resource_id(&id)?;
+        let subscription = self.form.get("subscription").trim().to_string();
+        if subscription.is_empty() {
+            return Err("Enter the subscription to snapshot".to_string());
+        }
+        let labels = parse_key_values(self.form.get("labels"))?;
+        validate_labels(&labels)?;
+        Ok(NewSnapshot {
+            id,
+            subscription,
+            labels,
+        })
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum SnapshotsEvent {
+    Select(Option<usize>),
+    /// Opens the create form, prefilled with a source subscription.
+    OpenCreate(String),
+    CloseCreate,
+    Form(FormEvent),
+    SubmitCreate,
+    Created(String, String),
+    ConfirmDelete(Option<String>),
+}
+
+impl From<SnapshotsEvent> for AppEvent {
+    fn from(event: SnapshotsEvent) -> Self {
+        AppEvent::Snapshots(event)
+    }
+}
+
+impl From<FormEvent> for SnapshotsEvent {
+    fn from(event: FormEvent) -> Self {
+        SnapshotsEvent::Form(event)
+    }
+}
+
+pub fn on_event(state: &mut Snapshots, event: SnapshotsEvent) -> Option<AppEvent> {
+    match event {
+        SnapshotsEvent::Select(idx) => {
+            state.selected = idx;
+            None
+        }
+        SnapshotsEvent::OpenCreate(subscription) => {
+            state.creating = true;
+            state.error = None;
+            state.form.set("id", String::new());
+            state.form.set("subscription", subscription);
+            state.form.set("labels", String::new());
+            Some(SnapshotsEvent::Form(FormEvent::Focus(Some("id".into()))).into())
+        }
+        SnapshotsEvent::CloseCreate => {
+            state.creating = false;
+            state.form.focused = None;
+            None
+        }
+        SnapshotsEvent::Form(e) => form::on_event(&mut state.form, e)
+            .map(SnapshotsEvent::from)
+            .map(AppEvent::from),
+        SnapshotsEvent::SubmitCreate => match state.new_snapshot() {
+            Ok(snapshot) => {
+                state.creating = false;
+                state.form.focused = None;
+                Some(PubsubEvent::CreateSnapshot(snapshot).into())
+            }
+            Err(e) => {
+                state.error = Some(e);
+                None
+            }
+        },
+        SnapshotsEvent::Created(name, subscription) => {
+            state.sources.insert(name, subscription);
+            Some(PubsubEvent::GetSnapshots.into())
+        }
+        SnapshotsEvent::ConfirmDelete(name) => {
+            state.deleting = name;
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(pubsub: &Pubsub, key: KeyEvent) -> InputHandled<AppEvent> {
+    let state = &pubsub.snapshots;
+    if state.creating {
+        let form_handled = form::on_key(&state.form, key)
+            .map(SnapshotsEvent::from)
+            .map(AppEvent::from);
+        if form_handled.is_handled() {
+            return form_handled;
+        }
+        return match key.code {
+            Enter => handled(SnapshotsEvent::SubmitCreate.into()),
+            Esc => handled(SnapshotsEvent::CloseCreate.into()),
+            _ => handled_empty(),
+        };
+    }
+    if let Some(name) = &state.deleting {
+        return match key.code {
+            Char('y') => handled(PubsubEvent::DeleteSnapshot(name.clone()).into()),
+            Char('n') | Esc => handled(SnapshotsEvent::ConfirmDelete(None).into()),
+            _ => handled_empty(),
+        };
+    }
+
+    let count = state.all.len();
+    match key.code {
+        Up | Down if count == 0 => not_handled(),
+        Up => {
+            let idx = state
+                .selected
+                .map_or(count - 1, |i| (i + count - 1) % count);
+            handled(SnapshotsEvent::Select(Some(idx)).into())
+        }
+        Down => {
+            let idx = state.selected.map_or(0, |i| (i + 1) % count);
+            handled(SnapshotsEvent::Select(Some(idx)).into())
+        }
+        Char('r') => handled(PubsubEvent::GetSnapshots.into()),
+        Char('c') => {
+            let subscription = pubsub
+                .subscriptions
+                .selected()
+                .map(|s| s.name.clone())
+                .unwrap_or_default();
+            handled(SnapshotsEvent::OpenCreate(subscription).into())
+        }
+        Char('d') => match state.selected() {
+            Some(snapshot) => {
+                handled(SnapshotsEvent::ConfirmDelete(Some(snapshot.name.clone())).into())
+            }
+            None => not_handled(),
+        },
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const TITLE: &str = "Snapshots (↑/↓ select, r refresh, c create, d delete)";
+const VIEWING_HELP: &str = "↑/↓ to navigate, Spacebar to edit, Enter to create, Esc to close";
+const EDITING_HELP: &str = "Editing: Press Enter to save, Esc to cancel";
+
+pub fn draw(state: &App, f: &mut Frame, area: Rect) {
+    let snapshots = &state.pubsub.snapshots;
+    let block = Block::default()
+        .title(TITLE)
+        .fg(Color::LightYellow)
+        .bg(Color::Black)
+        .borders(Borders::ALL);
+    f.render_widget(block, area);
+
+    let [list_area, details_area] = Layout::default()
+        .margin(1)
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .areas(area);
+    draw_list(snapshots, f, list_area);
+    match snapshots.creating {
+        true => draw_create(snapshots, f, details_area),
+        false => draw_details(snapshots, f, details_area),
+    }
+}
+
+fn draw_list(snapshots: &Snapshots, f: &mut Frame, area: Rect) {
+    let header = Row::new(vec!["Snapshot", "Topic", "Expires"])
+        .style(Style::default().bold().fg(Color::LightCyan));
+    let rows = snapshots.all.iter().map(|s| {
+        Row::new(vec![
+            short_name(&s.name).to_string(),
+            short_name(&s.topic).to_string(),
+            s.expire_time.map(format_timestamp).unwrap_or_default(),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+            Constraint::Min(24),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(Block::default().borders(Borders::ALL));
+    let mut table_state = TableState::default().with_selected(snapshots.selected);
+    f.render_stateful_widget(table, area, &mut table_state);
+}
+
+fn draw_details(snapshots: &Snapshots, f: &mut Frame, area: Rect) {
+    let block = Block::default().title("Details").borders(Borders::ALL);
+    let Some(snapshot) = snapshots.selected() else {
+        let empty = Paragraph::new("Select a snapshot to see its details").fg(Color::Gray);
+        f.render_widget(empty.block(block), area);
+        return;
+    };
+
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    let mut lines = Vec::new();
+    if snapshots.deleting.as_ref() == Some(&snapshot.name) {
+        lines.push(
+            Line::from(format!(
+                "Delete {}? Subscriptions can no longer seek to it. (y/n)",
+                short_name(&snapshot.name)
+            ))
+            .fg(Color::LightRed),
+        );
+    }
+    lines.extend([
+        field("Name", snapshot.name.clone()),
+        field("Topic", snapshot.topic.clone()),
+        field(
+            "Source subscription",
+            snapshots
+                .sources
+                .get(&snapshot.name)
+                .cloned()
+                .unwrap_or("Unknown (created outside this session)".to_string()),
+        ),
+        field(
+            "Expires",
+            snapshot
+                .expire_time
+                .map(format_timestamp)
+                .unwrap_or("Unknown".to_string()),
+        ),
+        field(
+            "Labels",
+            match snapshot.labels.is_empty() {
+                true => "None".to_string(),
+                false => String::new(),
+            },
+        ),
+    ]);
+    for (k, v) in &snapshot.labels {
+        lines.push(Line::raw(format!("  {k} = {v}")));
+    }
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+fn draw_create(snapshots: &Snapshots, f: &mut Frame, area: Rect) {
+    let block = Block::default()
+        .title("Create snapshot")
+        .borders(Borders::ALL)
+        .border_style(Style::default().bold());
+    f.render_widget(block, area);
+
+    let [help_area, fields_area] = Layout::vertical([Constraint::Length(2), Constraint::Min(0)])
+        .margin(1)
+        .areas(area);
+    let mut help = vec![Line::from(match snapshots.form.is_editing() {
+        true => EDITING_HELP,
+        false => VIEWING_HELP,
+    })
+    .fg(Color::Gray)];
+    if let Some(error) = &snapshots.error {
+        help.push(Line::from(error.as_str()).fg(Color::LightRed));
+    }
+    f.render_widget(Paragraph::new(Text::from(help)), help_area);
+    form::draw(&snapshots.form, f, fields_area);
+}
@@ -0,0 +1,249 @@
+use crate::{
+    app::App,
+    component::{
+        copy::{self, CopyEvent},
+        dead_letters::{self, DeadLettersEvent},
+        probe::{self, ProbeEvent},
+        pubsub::PubsubEvent,
+        seek::{self, SeekEvent},
+    },
+    event::AppEvent,
+    input::{handled, not_handled, InputHandled},
+    route::{select_route, Route},
+    util::short_name,
+};
+use google_cloud_pubsub::subscription::SubscriptionConfig;
+use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Enter, Up},
+        KeyEvent,
+    },
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Borders, Row, Table, TableState},
+    Frame,
+};
+use strum_macros::Display;
+
+// ===============
+// ==== STATE ====
+// ===============
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionInfo {
+    pub name: String,
+    pub topic: String,
+    pub ack_deadline_seconds: i32,
+    pub kind: SubscriptionKind,
+    pub filter: String,
+    /// Empty unless the subscription has a dead-letter policy.
+    pub dead_letter_topic: String,
+    pub max_delivery_attempts: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum SubscriptionKind {
+    #[strum(serialize = "Pull")]
+    Pull,
+    #[strum(serialize = "Push")]
+    Push,
+    #[strum(serialize = "Export")]
+    Export,
+}
+
+impl SubscriptionInfo {
+    pub fn new(name: String, topic: String, config: &SubscriptionConfig) -> Self {
+        let kind = if config.bigquery_config.is_some() {
+            SubscriptionKind::Export
+        } else if config
+            .push_config
+            .as_ref()
+            .is_some_and(|p| !p.push_endpoint.is_empty())
+        {
+            SubscriptionKind::Push
+        } else {
+            SubscriptionKind::Pull
+        };
+        Self {
+            name,
+            topic,
+            ack_deadline_seconds: config.ack_deadline_seconds,
+            kind,
+            filter: config.filter.clone(),
+            dead_letter_topic: config
+                .dead_letter_policy
+                .as_ref()
+                .map(|p| p.dead_letter_topic.clone())
+                .unwrap_or_default(),
+            max_delivery_attempts: config
+                .dead_letter_policy
+                .as_ref()
+                .map_or(0, |p| p.max_delivery_attempts),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Subscriptions {
+    pub all: Vec<SubscriptionInfo>,
+    pub selected: Option<usize>,
+}
+
+impl Subscriptions {
+    pub fn set_subscriptions(&mut self, subscriptions: Vec<SubscriptionInfo>) {
+        self.all = subscriptions;
+        self.selected = match self.all.is_empty() {
+            true => None,
+            false => Some(self.selected.unwrap_or(0).min(self.all.len() - 1)),
+        };
+    }
+
+    pub fn selected(&self) -> Option<&SubscriptionInfo> {
+        self.selected.and_then(|i| self.all.get(i))
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum SubscriptionsEvent {
+    Select(Option<usize>),
+}
+
+impl From<SubscriptionsEvent> for AppEvent {
+    fn from(event: SubscriptionsEvent) -> Self {
+        AppEvent::Subscriptions(event)
+    }
+}
+
+pub fn on_event(state: &mut Subscriptions, event: SubscriptionsEvent) -> Option<AppEvent> {
+    match event {
+        SubscriptionsEvent::Select(idx) => {
+            state.selected = idx;
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+pub fn on_key(state: &Subscriptions, key: KeyEvent) -> InputHandled<AppEvent> {
+    let count = state.all.len();
+    match key.code {
+        Up | Down if count == 0 => not_handled(),
+        Up => {
+            let idx = state
+                .selected
+                .map_or(count - 1, |i| (i + count - 1) % count);
+            handled(SubscriptionsEvent::Select(Some(idx)).into())
+        }
+        Down => {
+            let idx = state.selected.map_or(0, |i| (i + 1) % count);
+            handled(SubscriptionsEvent::Select(Some(idx)).into())
+        }
+        Char('r') => handled(PubsubEvent::GetSubscriptions.into()),
+        Enter if state.selected.is_some() => handled(select_route(Route::Messages)),
+        Char('C') => match state.selected() {
+            Some(s) => handled(CopyEvent::Open(s.name.clone()).into()),
+            None => not_handled(),
+        },
+        Char('L') => match state.selected() {
+            Some(s) if !s.dead_letter_topic.is_empty() => {
+                handled(DeadLettersEvent::Open(s.name.clone()).into())
+            }
+            _ => not_handled(),
+        },
+        Char('P') => match state.selected() {
+            Some(s) => handled(ProbeEvent::Open(s.name.clone(), s.topic.clone()).into()),
+            None => not_handled(),
+        },
+        Char('s') => match state.selected() {
+            Some(s) => handled(SeekEvent::Open(s.name.clone(), s.topic.clone()).into()),
+            None => not_handled(),
+        },
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const TITLE: &str =
+    "Subscriptions (↑/↓ select, r refresh, Enter browse messages, s seek, C copy to topic, L dead letters, P latency probe)";
+pub fn draw(state: &App, f: &mut Frame, area: Rect) {
+    let subscriptions = &state.pubsub.subscriptions;
+    let mut title = TITLE.to_string();
+    for summary in [
+        copy::summary(&state.pubsub.copier),
+        probe::summary(&state.pubsub.probe),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        title = format!("{title} {summary}");
+    }
+    let header = Row::new(vec![
+        "Subscription",
+        "Topic",
+        "Ack deadline",
+        "Type",
+        "Filter",
+    ])
+    .style(Style::default().bold().fg(Color::LightCyan));
+    let rows = subscriptions.all.iter().map(|s| {
+        Row::new(vec![
+            short_name(&s.name).to_string(),
+            short_name(&s.topic).to_string(),
+            format!("{}s", s.ack_deadline_seconds),
+            s.kind.to_string(),
+            s.filter.clone(),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(25),
+            Constraint::Percentage(25),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Min(0),
+        ],
+    )
+    .header(header)
+    .row_highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(
+        Block::default()
+            .title(title)
+            .fg(Color::LightYellow)
+            .bg(Color::Black)
+            .borders(Borders::ALL),
+    );
+    let mut table_state = TableState::default().with_selected(subscriptions.selected);
+    if !state.seek.is_open()
+        && !state.pubsub.copier.is_open()
+        && !state.pubsub.dead_letters.is_open()
+        && !state.pubsub.probe.is_open()
+    {
+        f.render_stateful_widget(table, area, &mut table_state);
+        return;
+    }
+    let [table_area, seek_area] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .areas(area);
+    f.render_stateful_widget(table, table_area, &mut table_state);
+    if state.seek.is_open() {
+        seek::draw(&state.seek, f, seek_area);
+    } else if state.pubsub.copier.is_open() {
+        copy::draw(&state.pubsub.copier, f, seek_area);
+    } else if state.pubsub.probe.is_open() {
+        probe::draw(&state.pubsub.probe, f, seek_area);
+    } else {
+        dead_letters::draw(&state.pubsub.dead_letters, f, seek_area);
+    }
+}
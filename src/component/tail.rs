@@ -0,0 +1,271 @@
+use crate::{
+    app::App,
+    component::{
+        decoders::decode_messages,
+        messages::{AckState, MessageInfo},
+        pubsub::{Pubsub, PubsubEvent},
+    },
+    event::{send_event, AppEvent},
+};
+use futures::{FutureExt, StreamExt};
+use google_cloud_pubsub::subscription::{MessageStream, Subscription};
+use std::time::{Duration, Instant};
+use tokio::{
+    sync::watch,
+    task::JoinHandle,
+    time::{interval, MissedTickBehavior},
+};
+
+// ===============
+// ==== STATE ====
+// ===============
+
+/// Settings the UI can change while the stream task is running.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TailControl {
+    pub paused: bool,
+    pub auto_ack: bool,
+}
+
+/// A streaming pull running in a background task. Received messages are
+/// sent back over the app event channel in batches as `TailEvent::Received`.
+pub struct Tail {
+    pub subscription: Option<String>,
+    pub control: TailControl,
+    pub received: u64,
+    pub rate: f64,
+    /// Counts the tails started, so that events still queued from a stopped
+    /// one are ignored.
+    run: u64,
+    task: Option<(JoinHandle<()>, watch::Sender<TailControl>)>,
+    window_count: u64,
+    window_start: Instant,
+}
+
+impl Default for Tail {
+    fn default() -> Self {
+        Self {
+            subscription: None,
+            control: TailControl::default(),
+            received: 0,
+            rate: 0.0,
+            run: 0,
+            task: None,
+            window_count: 0,
+            window_start: Instant::now(),
+        }
+    }
+}
+
+impl Tail {
+    pub fn is_running(&self) -> bool {
+        self.task.is_some()
+    }
+
+    pub fn start(&mut self, subscription: Subscription) {
+        self.stop();
+        let (control_tx, control_rx) = watch::channel(self.control);
+        self.subscription = Some(subscription.fully_qualified_name().to_string());
+        self.received = 0;
+        self.rate = 0.0;
+        self.window_count = 0;
+        self.window_start = Instant::now();
+        self.run += 1;
+        self.task = Some((spawn_stream(self.run, subscription, control_rx), control_tx));
+    }
+
+    pub fn stop(&mut self) {
+        if let Some((handle, _)) = self.task.take() {
+            // Dropping the stream inside the task cancels the streaming pull.
+            handle.abort();
+        }
+        self.rate = 0.0;
+    }
+
+    fn set_control(&mut self, control: TailControl) {
+        self.control = control;
+        if let Some((_, control_tx)) = &self.task {
+            let _ = control_tx.send(control);
+        }
+    }
+}
+
+/// How often received messages are sent to the UI. Sending them one at a
+/// time would crowd key presses and ticks out of the app event channel.
+const BATCH_INTERVAL: Duration = Duration::from_millis(100);
+const MAX_BATCH: usize = 500;
+
+/// Streams until stopped. While paused the stream is closed, so messages
+/// aren't held leased by a queue nobody reads.
+fn spawn_stream(
+    run: u64,
+    subscription: Subscription,
+    mut control: watch::Receiver<TailControl>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let name = subscription.fully_qualified_name().to_string();
+        let mut ticker = interval(BATCH_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut batch = Vec::new();
+        loop {
+            while control.borrow().paused {
+                if control.changed().await.is_err() {
+                    return;
+                }
+            }
+            let mut stream = match subscription.subscribe(None).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let error = Some(e.message().to_string());
+                    send_event(TailEvent::Stopped(run, error).into()).await;
+                    return;
+                }
+            };
+            loop {
+                tokio::select! {
+                    message = stream.next() => {
+                        let Some(message) = message else {
+                            send_batch(run, &name, &mut batch).await;
+                            send_event(TailEvent::Stopped(run, None).into()).await;
+                            return;
+                        };
+                        let mut info = MessageInfo::from(&message);
+                        let auto_ack = control.borrow().auto_ack;
+                        if auto_ack && message.ack().await.is_ok() {
+                            info.state = AckState::Acked;
+                        }
+                        batch.push(info);
+                        if batch.len() >= MAX_BATCH {
+                            send_batch(run, &name, &mut batch).await;
+                        }
+                    }
+                    _ = ticker.tick() => send_batch(run, &name, &mut batch).await,
+                    changed = control.changed() => {
+                        if changed.is_err() {
+                            return;
+                        }
+                        if control.borrow().paused {
+                            send_batch(run, &name, &mut batch).await;
+                            release(stream).await;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+async fn send_batch(run: u64, subscription: &str, batch: &mut Vec<MessageInfo>) {
+    if !batch.is_empty() {
+        let messages = std::mem::take(batch);
+        send_event(TailEvent::Received(run, subscription.to_string(), messages).into()).await;
+    }
+}
+
+/// Closes the stream and nacks the messages it already received, so they
+/// are redelivered right away rather than when their lease runs out.
+/// Messages that arrive after it is closed are nacked by the client.
+async fn release(mut stream: MessageStream) {
+    while let Some(Some(message)) = stream.next().now_or_never() {
+        let _ = message.nack().await;
+    }
+}
+
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
+pub enum TailEvent {
+    /// Messages received by the given run of the tail, on a subscription.
+    Received(u64, String, Vec<MessageInfo>),
+    Stopped(u64, Option<String>),
+    Stop,
+    TogglePause,
+    ToggleAutoAck,
+}
+
+impl From<TailEvent> for AppEvent {
+    fn from(event: TailEvent) -> Self {
+        AppEvent::Tail(event)
+    }
+}
+
+pub fn on_event(state: &mut Pubsub, event: TailEvent) -> Option<AppEvent> {
+    match event {
+        TailEvent::Received(run, _, _) | TailEvent::Stopped(run, _) if run != state.tail.run => {}
+        TailEvent::Received(_, subscription, mut messages) => {
+            state.tail.received += messages.len() as u64;
+            state.tail.window_count += messages.len() as u64;
+            let incomplete = decode_messages(state, &subscription, &mut messages);
+            for (released, ack_ids) in state.messages.add_messages(subscription.clone(), messages) {
+                tokio::spawn(send_event(PubsubEvent::Nack(released, ack_ids).into()));
+            }
+            if incomplete {
+                return Some(PubsubEvent::FetchDecodingSchemas(subscription).into());
+            }
+        }
+        TailEvent::Stopped(_, error) => {
+            state.tail.stop();
+            state.status.info = Some(match error {
+                Some(e) => format!("Tail stopped: {}", e),
+                None => "Tail stopped".to_string(),
+            });
+        }
+        TailEvent::Stop => {
+            state.tail.stop();
+            state.status.info = Some("Tail stopped".to_string());
+        }
+        TailEvent::TogglePause => {
+            let control = TailControl {
+                paused: !state.tail.control.paused,
+                ..state.tail.control
+            };
+            state.tail.set_control(control);
+        }
+        TailEvent::ToggleAutoAck => {
+            let control = TailControl {
+                auto_ack: !state.tail.control.auto_ack,
+                ..state.tail.control
+            };
+            state.tail.set_control(control);
+        }
+    }
+    None
+}
+
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+pub fn on_tick(state: &mut App) -> Option<AppEvent> {
+    let tail = &mut state.pubsub.tail;
+    let elapsed = tail.window_start.elapsed();
+    if elapsed >= RATE_WINDOW {
+        tail.rate = tail.window_count as f64 / elapsed.as_secs_f64();
+        tail.window_count = 0;
+        tail.window_start = Instant::now();
+    }
+    None
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+/// One-line summary for the Messages title, e.g. `● tailing 12.0 msg/s, auto-ack`.
+pub fn summary(state: &Tail) -> Option<String> {
+    if !state.is_running() {
+        return None;
+    }
+    let mode = match state.control.auto_ack {
+        true => "auto-ack",
+        false => "hold",
+    };
+    Some(match state.control.paused {
+        true => format!("⏸ paused, {} received, {}", state.received, mode),
+        false => format!(
+            "● tailing {:.1} msg/s, {} received, {}",
+            state.rate, state.received, mode
+        ),
+    })
+}
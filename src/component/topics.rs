@@ -1,14 +1,78 @@
-use crate::app::App;
+use crate::{
+    app::App,
+    component::{
+        bulk_publish::{self, BulkPublishEvent},
+        create_subscription::{self, CreateSubscriptionEvent},
+        create_topic::{self, CreateTopicEvent},
+        delete_topic::{self, DeleteTopicEvent},
+        load_test::{self, LoadTestEvent},
+        publish::{self, open_composer},
+        pubsub::{Pubsub, PubsubEvent},
+        schemas::SchemasEvent,
+    },
+    event::AppEvent,
+    input::{handled, not_handled, InputHandled},
+    util::{format_duration, short_name},
+};
+use google_cloud_googleapis::pubsub::v1::{Encoding, SchemaSettings};
 use ratatui::{
+    crossterm::event::{
+        KeyCode::{Char, Down, Esc, Up},
+        KeyEvent,
+    },
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Stylize},
-    widgets::{Block, Borders, List, ListState},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, List, ListState, Paragraph, Wrap},
     Frame,
 };
+use std::{collections::BTreeMap, time::Duration};
 
 #[derive(Debug, Clone)]
 pub struct TopicInfo {
     pub name: String,
+    pub details: Option<TopicDetails>,
+}
+
+impl TopicInfo {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            details: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TopicDetails {
+    pub labels: BTreeMap<String, String>,
+    pub message_retention: Option<Duration>,
+    pub schema: Option<TopicSchema>,
+    pub kms_key_name: String,
+    pub allowed_persistence_regions: Vec<String>,
+    pub subscriptions: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TopicSchema {
+    pub name: String,
+    pub encoding: String,
+    pub first_revision_id: String,
+    pub last_revision_id: String,
+}
+
+impl From<SchemaSettings> for TopicSchema {
+    fn from(settings: SchemaSettings) -> Self {
+        Self {
+            name: settings.schema,
+            encoding: Encoding::from_i32(settings.encoding)
+                .unwrap_or(Encoding::Unspecified)
+                .as_str_name()
+                .to_string(),
+            first_revision_id: settings.first_revision_id,
+            last_revision_id: settings.last_revision_id,
+        }
+    }
 }
 
 #[derive(Default)]
@@ -32,12 +96,33 @@ impl Topics {
         self.visibile = self.all.clone();
         self.list_state.select(None);
     }
+
+    pub fn set_details(&mut self, name: &str, details: TopicDetails) {
+        for topic in self.all.iter_mut().chain(self.visibile.iter_mut()) {
+            if topic.name == name {
+                topic.details = Some(details.clone());
+            }
+        }
+    }
+
+    pub fn selected(&self) -> Option<&TopicInfo> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.visibile.get(i))
+    }
 }
 
+// ================
+// ==== EVENTS ====
+// ================
+
+#[derive(Debug, Clone)]
 pub enum TopicsEvent {
     Select(usize),
     Deselect,
+    #[allow(dead_code)]
     Filter(String),
+    #[allow(dead_code)]
     ClearFilter,
 }
 
@@ -55,7 +140,104 @@ pub enum TopicsEvent {
 //     }
 // }
 
-const TITLE: &str = "Topics";
+impl From<TopicsEvent> for AppEvent {
+    fn from(event: TopicsEvent) -> Self {
+        AppEvent::Topics(event)
+    }
+}
+
+pub fn on_event(state: &mut Topics, event: TopicsEvent) -> Option<AppEvent> {
+    match event {
+        TopicsEvent::Select(idx) => {
+            state.list_state.select(Some(idx));
+            match state.selected() {
+                Some(topic) if topic.details.is_none() => {
+                    Some(PubsubEvent::GetTopicDetails(topic.name.clone()).into())
+                }
+                _ => None,
+            }
+        }
+        TopicsEvent::Deselect => {
+            state.list_state.select(None);
+            None
+        }
+        TopicsEvent::Filter(text) => {
+            state.visibile = state
+                .all
+                .iter()
+                .filter(|t| t.name.contains(&text))
+                .cloned()
+                .collect();
+            state.list_state.select(None);
+            None
+        }
+        TopicsEvent::ClearFilter => {
+            state.visibile = state.all.clone();
+            state.list_state.select(None);
+            None
+        }
+    }
+}
+
+// ===============
+// ==== INPUT ====
+// ===============
+
+/// Deleting only asks for the topic ID to be typed when not talking to the
+/// emulator, where topics are cheap to recreate.
+pub fn on_key(pubsub: &Pubsub, key: KeyEvent) -> InputHandled<AppEvent> {
+    let state = &pubsub.topics;
+    let count = state.visibile.len();
+    let selected = state.list_state.selected();
+    match key.code {
+        Up | Down if count == 0 => not_handled(),
+        Up => handled(
+            TopicsEvent::Select(selected.map_or(count - 1, |i| (i + count - 1) % count)).into(),
+        ),
+        Down => handled(TopicsEvent::Select(selected.map_or(0, |i| (i + 1) % count)).into()),
+        Esc if selected.is_some() => handled(TopicsEvent::Deselect.into()),
+        Char('r') => handled(PubsubEvent::GetTopics.into()),
+        Char('c') => handled(CreateTopicEvent::Open.into()),
+        Char('P') => match state.selected() {
+            Some(topic) => handled(BulkPublishEvent::Open(topic.name.clone()).into()),
+            None => not_handled(),
+        },
+        Char('b') => match state.selected() {
+            Some(topic) => handled(LoadTestEvent::Open(topic.name.clone()).into()),
+            None => not_handled(),
+        },
+        Char('p') => match state.selected() {
+            Some(topic) => handled(open_composer(topic.name.clone())),
+            None => not_handled(),
+        },
+        Char('s') => match state.selected() {
+            Some(topic) => handled(CreateSubscriptionEvent::Open(topic.name.clone()).into()),
+            None => not_handled(),
+        },
+        Char('g') => match state
+            .selected()
+            .and_then(|t| t.details.as_ref())
+            .and_then(|d| d.schema.as_ref())
+        {
+            Some(schema) => handled(SchemasEvent::Show(schema.name.clone()).into()),
+            None => not_handled(),
+        },
+        Char('d') => match state.selected() {
+            Some(topic) => {
+                handled(DeleteTopicEvent::Open(topic.name.clone(), !pubsub.emulator).into())
+            }
+            None => not_handled(),
+        },
+        _ => not_handled(),
+    }
+}
+
+// ==============
+// ==== VIEW ====
+// ==============
+
+const TITLE: &str =
+    "Topics (↑/↓ select, r refresh, p publish, P publish file, b load test, c create, d delete, s subscribe)";
 pub fn draw(state: &App, f: &mut Frame, area: Rect) {
     let block = Block::default()
         .title(TITLE.to_string())
@@ -64,14 +246,122 @@ pub fn draw(state: &App, f: &mut Frame, area: Rect) {
         .borders(Borders::ALL);
     f.render_widget(block, area);
 
-    let [list_area, _details_area] = Layout::default()
+    let [list_area, details_area] = Layout::default()
         .margin(1)
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
         .areas(area);
 
-    let list = List::new(state.topics.visibile.iter().map(|t| t.name.clone()))
-        .block(Block::default().borders(Borders::ALL));
+    let topics = &state.pubsub.topics;
+    let list = List::new(
+        topics
+            .visibile
+            .iter()
+            .map(|t| short_name(&t.name).to_string()),
+    )
+    .highlight_style(Style::default().bg(Color::LightYellow).fg(Color::Black))
+    .block(Block::default().borders(Borders::ALL));
 
-    f.render_widget(list, list_area);
+    let mut list_state = topics.list_state.clone();
+    f.render_stateful_widget(list, list_area, &mut list_state);
+    if state.composer.is_open() {
+        publish::draw(&state.composer, f, details_area);
+    } else if state.pubsub.bulk.is_open() {
+        bulk_publish::draw(&state.pubsub.bulk, f, details_area);
+    } else if state.pubsub.load_test.is_open() {
+        load_test::draw(&state.pubsub.load_test, f, details_area);
+    } else if state.create_topic.open {
+        create_topic::draw(&state.create_topic, f, details_area);
+    } else if state.create_subscription.is_open() {
+        create_subscription::draw(
+            &state.create_subscription,
+            &state.pubsub.messages,
+            f,
+            details_area,
+        );
+    } else if state.delete_topic.is_open() {
+        let details = topics.selected().and_then(|t| t.details.as_ref());
+        delete_topic::draw(&state.delete_topic, details, f, details_area);
+    } else {
+        draw_details(topics.selected(), f, details_area);
+    }
+}
+
+fn draw_details(topic: Option<&TopicInfo>, f: &mut Frame, area: Rect) {
+    let block = Block::default().title("Details").borders(Borders::ALL);
+    let text = match topic {
+        None => Text::from("Select a topic to see its details").fg(Color::Gray),
+        Some(TopicInfo { details: None, .. }) => Text::from("Loading...").fg(Color::Gray),
+        Some(TopicInfo {
+            name,
+            details: Some(details),
+        }) => details_text(name, details),
+    };
+    let paragraph = Paragraph::new(text).block(block).wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+fn details_text<'a>(name: &'a str, details: &'a TopicDetails) -> Text<'a> {
+    fn field<'a>(label: &'a str, value: String) -> Line<'a> {
+        Line::from(vec![
+            Span::styled(format!("{label}: "), Style::default().fg(Color::LightCyan)),
+            Span::raw(value),
+        ])
+    }
+    fn or_none(value: &str) -> String {
+        match value.is_empty() {
+            true => "None".to_string(),
+            false => value.to_string(),
+        }
+    }
+
+    let mut lines = vec![
+        field("Name", name.to_string()),
+        field(
+            "Message retention",
+            details
+                .message_retention
+                .map_or("Not set".to_string(), format_duration),
+        ),
+        field("KMS key", or_none(&details.kms_key_name)),
+        field(
+            "Storage regions",
+            or_none(&details.allowed_persistence_regions.join(", ")),
+        ),
+    ];
+    match &details.schema {
+        Some(schema) => {
+            lines.push(field("Schema", format!("{} (g to open)", schema.name)));
+            lines.push(field("  Encoding", schema.encoding.clone()));
+            lines.push(field(
+                "  Revisions",
+                format!(
+                    "{} .. {}",
+                    or_none(&schema.first_revision_id),
+                    or_none(&schema.last_revision_id)
+                ),
+            ));
+        }
+        None => lines.push(field("Schema", "None".to_string())),
+    }
+
+    lines.push(field(
+        "Labels",
+        match details.labels.is_empty() {
+            true => "None".to_string(),
+            false => String::new(),
+        },
+    ));
+    for (k, v) in &details.labels {
+        lines.push(Line::raw(format!("  {k} = {v}")));
+    }
+
+    lines.push(field(
+        "Subscriptions",
+        details.subscriptions.len().to_string(),
+    ));
+    for subscription in &details.subscriptions {
+        lines.push(Line::raw(format!("  {}", short_name(subscription))));
+    }
+    Text::from(lines)
 }
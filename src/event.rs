@@ -1,7 +1,25 @@
 use crate::app::App;
 use crate::component::{
+    bulk_publish::{self, BulkPublishEvent},
+    copy::{self, CopyEvent},
+    create_subscription::{self, CreateSubscriptionEvent},
+    create_topic::{self, CreateTopicEvent},
+    dead_letters::{self, DeadLettersEvent},
     debug::{self, debug_log, DebugLogsEvent},
+    decoders::{self, DecodersEvent},
+    delete_topic::{self, DeleteTopicEvent},
+    export::{self, ExportEvent},
+    load_test::{self, LoadTestEvent},
+    messages::{self, MessagesEvent},
+    probe::{self, ProbeEvent},
+    publish::{self, ComposerEvent},
     pubsub::{self, ConfigEvent, PubsubEvent},
+    schemas::{self, SchemasEvent},
+    seek::{self, SeekEvent},
+    snapshots::{self, SnapshotsEvent},
+    subscriptions::{self, SubscriptionsEvent},
+    tail::{self, TailEvent},
+    topics::{self, TopicsEvent},
 };
 use crate::input::{on_key, InputHandled};
 use crate::route;
@@ -30,6 +48,24 @@ pub enum AppEvent {
     Tick,
     Input(KeyEvent),
     Pubsub(PubsubEvent),
+    Topics(TopicsEvent),
+    Subscriptions(SubscriptionsEvent),
+    Composer(ComposerEvent),
+    BulkPublish(BulkPublishEvent),
+    LoadTest(LoadTestEvent),
+    CreateTopic(CreateTopicEvent),
+    DeleteTopic(DeleteTopicEvent),
+    CreateSubscription(CreateSubscriptionEvent),
+    Seek(SeekEvent),
+    Copy(CopyEvent),
+    DeadLetters(DeadLettersEvent),
+    Probe(ProbeEvent),
+    Decoders(DecodersEvent),
+    Export(ExportEvent),
+    Snapshots(SnapshotsEvent),
+    Schemas(SchemasEvent),
+    Messages(MessagesEvent),
+    Tail(TailEvent),
     Route(RouteEvent),
     Debug(DebugLogsEvent),
     Quit,
@@ -52,6 +88,30 @@ pub async fn on_event(state: &mut App, e: AppEvent) {
         AppEvent::Input(key) => on_key(state, key).await,
         AppEvent::Route(event) => route::on_event(state, event),
         AppEvent::Pubsub(pubsub_event) => pubsub::on_event(&mut state.pubsub, pubsub_event).await,
+        AppEvent::Topics(event) => topics::on_event(&mut state.pubsub.topics, event),
+        AppEvent::Subscriptions(event) => {
+            subscriptions::on_event(&mut state.pubsub.subscriptions, event)
+        }
+        AppEvent::Messages(event) => messages::on_event(&mut state.pubsub.messages, event),
+        AppEvent::Tail(event) => tail::on_event(&mut state.pubsub, event),
+        AppEvent::Composer(event) => publish::on_event(&mut state.composer, event),
+        AppEvent::BulkPublish(event) => {
+            bulk_publish::on_event(&mut state.pubsub, &state.composer, event)
+        }
+        AppEvent::LoadTest(event) => load_test::on_event(&mut state.pubsub, event),
+        AppEvent::CreateTopic(event) => create_topic::on_event(&mut state.create_topic, event),
+        AppEvent::DeleteTopic(event) => delete_topic::on_event(&mut state.delete_topic, event),
+        AppEvent::CreateSubscription(event) => {
+            create_subscription::on_event(&mut state.create_subscription, event)
+        }
+        AppEvent::Seek(event) => seek::on_event(&mut state.seek, event),
+        AppEvent::Copy(event) => copy::on_event(&mut state.pubsub, event),
+        AppEvent::DeadLetters(event) => dead_letters::on_event(&mut state.pubsub, event),
+        AppEvent::Probe(event) => probe::on_event(&mut state.pubsub, event),
+        AppEvent::Decoders(event) => decoders::on_event(&mut state.pubsub, event),
+        AppEvent::Export(event) => export::on_event(&mut state.pubsub, event),
+        AppEvent::Snapshots(event) => snapshots::on_event(&mut state.pubsub.snapshots, event),
+        AppEvent::Schemas(event) => schemas::on_event(&mut state.pubsub.schemas, event),
         AppEvent::Debug(event) => {
             debug::on_event(&mut state.debug_logs, event);
             None
@@ -61,9 +121,8 @@ pub async fn on_event(state: &mut App, e: AppEvent) {
     if let Some(ref chain) = ret {
         debug_log(format!("OUT {:?}", chain));
     }
-    match ret {
-        Some(event) => send_event(event).await,
-        None => {}
+    if let Some(event) = ret {
+        send_event(event).await;
     }
 }
 
@@ -71,7 +130,8 @@ pub fn on_tick(state: &mut App) -> Option<AppEvent> {
     state.ticks += 1;
     state.last_tick = std::time::Instant::now();
     debug::on_tick(state);
-    None
+    tail::on_tick(state);
+    dead_letters::on_tick(&mut state.pubsub)
 }
 
 pub fn on_quit(app: &mut App) -> Option<AppEvent> {
@@ -0,0 +1,414 @@
+//! Subscription filters, parsed and evaluated locally so they can be checked
+//! before a subscription is created.
+//!
+//! A filter combines conditions on attributes with `AND`, `OR` and `NOT`
+//! (or a leading `-`), and parentheses. `AND` and `OR` can't be mixed
+//! without parentheses. The conditions are:
+//!
+//! - `attributes:key`, the message has the attribute
+//! - `attributes.key = "value"` and `attributes.key != "value"`
+//! - `hasPrefix(attributes.key, "prefix")`
+//!
+//! Keys are letters, digits, `_` and `-`, or any text in double quotes.
+
+use std::collections::BTreeMap;
+
+/// Pub/Sub rejects longer filters.
+pub const MAX_LENGTH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Not(Box<Filter>),
+    Has(String),
+    Equals(String, String),
+    HasPrefix(String, String),
+}
+
+impl Filter {
+    pub fn matches(&self, attributes: &BTreeMap<String, String>) -> bool {
+        match self {
+            Filter::And(filters) => filters.iter().all(|f| f.matches(attributes)),
+            Filter::Or(filters) => filters.iter().any(|f| f.matches(attributes)),
+            Filter::Not(filter) => !filter.matches(attributes),
+            Filter::Has(key) => attributes.contains_key(key),
+            Filter::Equals(key, value) => attributes.get(key) == Some(value),
+            Filter::HasPrefix(key, prefix) => {
+                attributes.get(key).is_some_and(|v| v.starts_with(prefix))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+    Open,
+    Close,
+    Comma,
+    Dot,
+    Colon,
+    Equals,
+    NotEquals,
+    Minus,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Word(word) => format!("'{word}'"),
+            Token::Text(text) => format!("\"{text}\""),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Dot => "'.'".to_string(),
+            Token::Colon => "':'".to_string(),
+            Token::Equals => "'='".to_string(),
+            Token::NotEquals => "'!='".to_string(),
+            Token::Minus => "'-'".to_string(),
+            Token::End => "the end of the filter".to_string(),
+        }
+    }
+}
+
+/// Tokens with the 1-based column they start at.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let column = i + 1;
+        let token = match chars[i] {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => Token::Open,
+            ')' => Token::Close,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            ':' => Token::Colon,
+            '=' => Token::Equals,
+            '-' => Token::Minus,
+            '!' if chars.get(i + 1) == Some(&'=') => {
+                i += 1;
+                Token::NotEquals
+            }
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    i += 1;
+                    match chars.get(i) {
+                        None => return Err(format!("Unterminated string at column {column}")),
+                        Some('"') => break,
+                        Some('\\') => {
+                            i += 1;
+                            match chars.get(i) {
+                                Some(c) => text.push(*c),
+                                None => {
+                                    return Err(format!("Unterminated string at column {column}"))
+                                }
+                            }
+                        }
+                        Some(c) => text.push(*c),
+                    }
+                }
+                Token::Text(text)
+            }
+            c if is_word_char(c) => {
+                let start = i;
+                while chars.get(i + 1).is_some_and(|c| is_word_char(*c))
+                    || (chars.get(i + 1) == Some(&'-')
+                        && chars.get(i + 2).is_some_and(|c| is_word_char(*c)))
+                {
+                    i += 1;
+                }
+                Token::Word(chars[start..=i].iter().collect())
+            }
+            c => return Err(format!("Unexpected '{c}' at column {column}")),
+        };
+        tokens.push((column, token));
+        i += 1;
+    }
+    tokens.push((chars.len() + 1, Token::End));
+    Ok(tokens)
+}
+
+/// A `-` between word characters is part of the word, e.g. `x-type`, while
+/// one in front of a word negates it.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].1
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let token = self.tokens[self.position].clone();
+        if token.1 != Token::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        let (column, token) = &self.tokens[self.position];
+        format!(
+            "Expected {expected} at column {column}, found {}",
+            token.describe()
+        )
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<(), String> {
+        match *self.peek() == token {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Word(word) if word == keyword)
+    }
+
+    /// Conditions joined by only `AND`s or only `OR`s.
+    fn expression(&mut self) -> Result<Filter, String> {
+        let first = self.term()?;
+        let operator = match self.peek() {
+            Token::Word(word) if word == "AND" || word == "OR" => word.clone(),
+            _ => return Ok(first),
+        };
+        let mut terms = vec![first];
+        loop {
+            match self.peek() {
+                Token::Word(word) if *word == operator => {
+                    self.next();
+                    terms.push(self.term()?);
+                }
+                Token::Word(word) if word == "AND" || word == "OR" => {
+                    let column = self.tokens[self.position].0;
+                    return Err(format!(
+                        "Use parentheses to mix AND and OR, at column {column}"
+                    ));
+                }
+                _ => break,
+            }
+        }
+        Ok(match operator.as_str() {
+            "AND" => Filter::And(terms),
+            _ => Filter::Or(terms),
+        })
+    }
+
+    fn term(&mut self) -> Result<Filter, String> {
+        if self.is_keyword("NOT") {
+            self.next();
+            return Ok(Filter::Not(Box::new(self.term()?)));
+        }
+        match self.peek() {
+            Token::Minus => {
+                self.next();
+                Ok(Filter::Not(Box::new(self.term()?)))
+            }
+            Token::Open => {
+                self.next();
+                let filter = self.expression()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(filter)
+            }
+            Token::Word(word) if word == "hasPrefix" => {
+                self.next();
+                self.expect(Token::Open, "'(' after hasPrefix")?;
+                self.expect_attributes()?;
+                self.expect(Token::Dot, "'.' after attributes")?;
+                let key = self.key()?;
+                self.expect(Token::Comma, "','")?;
+                let prefix = self.text()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(Filter::HasPrefix(key, prefix))
+            }
+            Token::Word(word) if word == "attributes" => {
+                self.next();
+                match self.next() {
+                    (_, Token::Colon) => Ok(Filter::Has(self.key()?)),
+                    (_, Token::Dot) => {
+                        let key = self.key()?;
+                        let equals = match self.peek() {
+                            Token::Equals => true,
+                            Token::NotEquals => false,
+                            _ => return Err(self.unexpected("'=' or '!='")),
+                        };
+                        self.next();
+                        let condition = Filter::Equals(key, self.text()?);
+                        Ok(match equals {
+                            true => condition,
+                            false => Filter::Not(Box::new(condition)),
+                        })
+                    }
+                    (column, token) => Err(format!(
+                        "Expected '.' or ':' after attributes at column {column}, found {}",
+                        token.describe()
+                    )),
+                }
+            }
+            _ => Err(self.unexpected("a condition")),
+        }
+    }
+
+    fn expect_attributes(&mut self) -> Result<(), String> {
+        match self.is_keyword("attributes") {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => Err(self.unexpected("'attributes'")),
+        }
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        match self.peek().clone() {
+            Token::Word(key) | Token::Text(key) => {
+                self.next();
+                Ok(key)
+            }
+            _ => Err(self.unexpected("an attribute key")),
+        }
+    }
+
+    fn text(&mut self) -> Result<String, String> {
+        match self.peek().clone() {
+            Token::Text(text) => {
+                self.next();
+                Ok(text)
+            }
+            _ => Err(self.unexpected("a quoted string")),
+        }
+    }
+}
+
+/// Parses a non-empty filter, or explains what's wrong with it and where.
+pub fn parse(input: &str) -> Result<Filter, String> {
+    if input.len() > MAX_LENGTH {
+        return Err(format!(
+            "Filters can be at most {MAX_LENGTH} bytes, this one is {}",
+            input.len()
+        ));
+    }
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+    };
+    let filter = parser.expression()?;
+    match parser.peek() {
+        Token::End => Ok(filter),
+        _ => Err(parser.unexpected("AND, OR or the end of the filter")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attributes(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn not_binds_tighter_than_and_or() {
+        assert_eq!(
+            parse("NOT attributes:a AND attributes:b"),
+            Ok(Filter::And(vec![
+                Filter::Not(Box::new(Filter::Has("a".into()))),
+                Filter::Has("b".into()),
+            ]))
+        );
+        assert_eq!(
+            parse("-(attributes:a OR attributes:b)"),
+            Ok(Filter::Not(Box::new(Filter::Or(vec![
+                Filter::Has("a".into()),
+                Filter::Has("b".into()),
+            ]))))
+        );
+        assert_eq!(parse("-attributes:a"), parse("NOT attributes:a"),);
+    }
+
+    #[test]
+    fn parentheses_group_mixed_operators() {
+        let filter = parse("attributes:a AND (attributes:b OR attributes:c)").unwrap();
+        assert!(filter.matches(&attributes(&[("a", ""), ("c", "")])));
+        assert!(!filter.matches(&attributes(&[("b", ""), ("c", "")])));
+    }
+
+    #[test]
+    fn mixing_and_or_without_parentheses_is_rejected() {
+        assert_eq!(
+            parse("attributes:a AND attributes:b OR attributes:c"),
+            Err("Use parentheses to mix AND and OR, at column 31".to_string())
+        );
+    }
+
+    #[test]
+    fn quoted_keys_and_dashed_words() {
+        assert_eq!(
+            parse(r#"attributes."key with spaces" = "v""#),
+            Ok(Filter::Equals("key with spaces".into(), "v".into()))
+        );
+        assert_eq!(
+            parse(r#"hasPrefix(attributes.x-type, "order\"s")"#),
+            Ok(Filter::HasPrefix("x-type".into(), "order\"s".into()))
+        );
+    }
+
+    #[test]
+    fn not_equals_matches_a_missing_attribute() {
+        let filter = parse(r#"attributes.region != "eu""#).unwrap();
+        assert!(filter.matches(&attributes(&[])));
+        assert!(filter.matches(&attributes(&[("region", "us")])));
+        assert!(!filter.matches(&attributes(&[("region", "eu")])));
+    }
+
+    #[test]
+    fn filters_are_limited_to_256_bytes() {
+        let key = "k".repeat(MAX_LENGTH - "attributes:".len());
+        assert!(parse(&format!("attributes:{key}")).is_ok());
+        assert_eq!(
+            parse(&format!("attributes:{key}k")),
+            Err("Filters can be at most 256 bytes, this one is 257".to_string())
+        );
+    }
+
+    #[test]
+    fn errors_give_the_column() {
+        assert_eq!(
+            parse("attributes.a = x"),
+            Err("Expected a quoted string at column 16, found 'x'".to_string())
+        );
+        assert_eq!(
+            parse(r#"attributes.a = "x"#),
+            Err("Unterminated string at column 16".to_string())
+        );
+        assert_eq!(
+            parse("attributes:a )"),
+            Err("Expected AND, OR or the end of the filter at column 14, found ')'".to_string())
+        );
+        assert_eq!(
+            parse("attributes:a AND"),
+            Err("Expected a condition at column 17, found the end of the filter".to_string())
+        );
+    }
+}
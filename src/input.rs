@@ -1,7 +1,9 @@
 use crate::app::App;
 use crate::component::{
-    debug::{debug_log, debug_logs_clear, toggle_debug_logs},
-    pubsub,
+    bulk_publish, copy, create_subscription, create_topic, dead_letters,
+    debug::{debug_log, toggle_debug_logs},
+    decoders, delete_topic, export, load_test, messages, probe, publish, pubsub, schemas, seek,
+    snapshots, subscriptions, topics,
 };
 use crate::event::{quit, AppEvent};
 use crate::route::{next_route, previous_route, select_route, Route};
@@ -22,7 +24,39 @@ pub async fn on_key(state: &App, key: KeyEvent) -> Option<AppEvent> {
 
     let route_result = match state.route {
         Route::Config => pubsub::on_key(&state.pubsub.config, key),
-        _ => not_handled(),
+        Route::Topics if state.composer.is_open() => publish::on_key(&state.composer, key),
+        Route::Topics if state.pubsub.bulk.is_open() => {
+            bulk_publish::on_key(&state.pubsub.bulk, key)
+        }
+        Route::Topics if state.pubsub.load_test.is_open() => {
+            load_test::on_key(&state.pubsub.load_test, key)
+        }
+        Route::Topics if state.create_topic.open => create_topic::on_key(&state.create_topic, key),
+        Route::Topics if state.delete_topic.is_open() => {
+            delete_topic::on_key(&state.delete_topic, key)
+        }
+        Route::Topics if state.create_subscription.is_open() => {
+            create_subscription::on_key(&state.create_subscription, key)
+        }
+        Route::Topics => topics::on_key(&state.pubsub, key),
+        Route::Subscriptions if state.seek.is_open() => seek::on_key(&state.seek, key),
+        Route::Subscriptions if state.pubsub.copier.is_open() => {
+            copy::on_key(&state.pubsub.copier, key)
+        }
+        Route::Subscriptions if state.pubsub.probe.is_open() => {
+            probe::on_key(&state.pubsub.probe, key)
+        }
+        Route::Subscriptions if state.pubsub.dead_letters.is_open() => {
+            dead_letters::on_key(&state.pubsub.dead_letters, key)
+        }
+        Route::Subscriptions => subscriptions::on_key(&state.pubsub.subscriptions, key),
+        Route::Messages if state.pubsub.decoders.open => {
+            decoders::on_key(&state.pubsub.decoders, key)
+        }
+        Route::Messages if state.pubsub.export.open => export::on_key(&state.pubsub.export, key),
+        Route::Messages => messages::on_key(&state.pubsub, key),
+        Route::Snapshots => snapshots::on_key(&state.pubsub, key),
+        Route::Schemas => schemas::on_key(&state.pubsub, key),
     };
 
     if route_result.is_handled() {
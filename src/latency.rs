@@ -0,0 +1,107 @@
+//! Latency samples counted in logarithmic buckets, so that long runs keep a
+//! fixed amount of memory while percentiles stay within an eighth of the
+//! true value.
+
+use std::time::Duration;
+
+/// Buckets below this many microseconds hold a single value each.
+const LINEAR: u64 = 8;
+
+#[derive(Debug, Clone, Default)]
+pub struct Latencies {
+    counts: Vec<u64>,
+    total: u64,
+    max: Duration,
+}
+
+/// Every power of two is split into `LINEAR` buckets of equal width.
+fn bucket(micros: u64) -> usize {
+    if micros < LINEAR {
+        return micros as usize;
+    }
+    let exponent = 63 - micros.leading_zeros() as u64;
+    let sub = (micros >> (exponent - 3)) & (LINEAR - 1);
+    ((exponent - 2) * LINEAR + sub) as usize
+}
+
+/// The smallest value counted in the bucket.
+fn lower_bound(bucket: usize) -> u64 {
+    let bucket = bucket as u64;
+    if bucket < LINEAR {
+        return bucket;
+    }
+    let exponent = bucket / LINEAR + 2;
+    let bound = ((LINEAR + bucket % LINEAR) as u128) << (exponent - 3);
+    bound.min(u64::MAX as u128) as u64
+}
+
+impl Latencies {
+    pub fn record(&mut self, latency: Duration) {
+        let index = bucket(latency.as_micros().min(u64::MAX as u128) as u64);
+        if self.counts.len() <= index {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        self.total += 1;
+        self.max = self.max.max(latency);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.total
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    pub fn merge(&mut self, other: &Latencies) {
+        if self.counts.len() < other.counts.len() {
+            self.counts.resize(other.counts.len(), 0);
+        }
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+        self.total += other.total;
+        self.max = self.max.max(other.max);
+    }
+
+    /// How many samples fall below each of the ascending `bounds`, and above
+    /// the last one. Samples are placed by the start of their bucket.
+    pub fn histogram(&self, bounds: &[Duration]) -> Vec<u64> {
+        let mut bins = vec![0; bounds.len() + 1];
+        for (index, count) in self.counts.iter().enumerate() {
+            let start = Duration::from_micros(lower_bound(index));
+            let bin = bounds.partition_point(|bound| *bound <= start);
+            bins[bin] += count;
+        }
+        bins
+    }
+
+    /// The latency below which `percentile` percent of the samples fall,
+    /// rounded up to the end of its bucket. `None` without samples.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.total == 0 {
+            return None;
+        }
+        let rank = ((percentile / 100.0) * self.total as f64).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                let upper = Duration::from_micros(lower_bound(index + 1));
+                return Some(upper.min(self.max));
+            }
+        }
+        Some(self.max)
+    }
+}
+
+/// Formats a latency with a unit that suits it, e.g. `850µs`, `12.3ms`, `1.20s`.
+pub fn format_latency(latency: Duration) -> String {
+    let micros = latency.as_micros();
+    match micros {
+        0..=999 => format!("{micros}µs"),
+        1_000..=999_999 => format!("{:.1}ms", micros as f64 / 1_000.0),
+        _ => format!("{:.2}s", latency.as_secs_f64()),
+    }
+}
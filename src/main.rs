@@ -3,10 +3,15 @@ use std::{error::Error, time::Duration};
 use tokio::{sync::mpsc, time};
 
 mod app;
+mod codec;
 mod component;
 mod event;
+mod filter;
 mod input;
+mod latency;
+mod records;
 mod route;
+mod util;
 mod view;
 
 use app::App;
@@ -59,10 +64,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
             break;
         }
 
-        match rx.recv().await {
-            Some(e) => on_event(&mut app, e).await,
-            None => {}
-        };
+        if let Some(e) = rx.recv().await {
+            on_event(&mut app, e).await;
+        }
 
         terminal.draw(|f| draw(&app, f))?;
     }
@@ -0,0 +1,315 @@
+//! Messages stored in files, one per line of NDJSON or one per CSV row.
+//!
+//! An NDJSON line is an object with the payload in `data` (a string, or any
+//! other JSON value which is published as compact JSON) or in `data_base64`,
+//! plus optional `attributes` and `ordering_key`. A CSV file has a header
+//! with a `payload` or `payload_base64` column, an optional `ordering_key`
+//! column, and every other column is an attribute. A directory holds one
+//! payload per file, published in file name order without attributes.
+//!
+//! Exported messages also carry their `message_id` and `publish_time`,
+//! which are ignored when the file is published again.
+
+use crate::{component::messages::MessageInfo, util::format_timestamp};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Ndjson,
+    Csv,
+    /// A directory with one payload per file.
+    Raw,
+}
+
+impl Format {
+    /// Directories are raw payloads, files ending in `.csv` are CSV and
+    /// anything else is NDJSON.
+    pub fn from_path(path: &Path) -> Self {
+        if path.is_dir() {
+            return Format::Raw;
+        }
+        match path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+        {
+            true => Format::Csv,
+            false => Format::Ndjson,
+        }
+    }
+
+    /// `path` with the extension `from_path` needs to read it back in this
+    /// format appended, if it doesn't have it already.
+    pub fn with_extension(self, path: PathBuf) -> PathBuf {
+        let extension = match (self, Format::from_path(&path)) {
+            (Format::Csv, Format::Ndjson) => "csv",
+            (Format::Ndjson, Format::Csv) => "ndjson",
+            _ => return path,
+        };
+        let mut path = path.into_os_string();
+        path.push(".");
+        path.push(extension);
+        path.into()
+    }
+}
+
+/// CSV columns that describe a message rather than being its attributes.
+const METADATA_COLUMNS: [&str; 2] = ["message_id", "publish_time"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Record {
+    pub data: Vec<u8>,
+    pub attributes: HashMap<String, String>,
+    pub ordering_key: String,
+}
+
+/// A record or why it couldn't be read, with the 1-based line it came from,
+/// or for raw payloads the position of the file.
+pub type Line = (usize, Result<Record, String>);
+
+/// Reads every record of the file. Only a file that can't be read at all is
+/// an error, lines that don't parse are returned as failed records.
+pub fn read(path: &Path) -> Result<Vec<Line>, String> {
+    let format = Format::from_path(path);
+    if format == Format::Raw {
+        return read_raw(path).map_err(|e| format!("{}: {}", path.display(), e));
+    }
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    match format {
+        Format::Csv => read_csv(&text),
+        _ => Ok(read_ndjson(&text)),
+    }
+}
+
+fn read_raw(dir: &Path) -> std::io::Result<Vec<Line>> {
+    let mut files = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    files.retain(|path| path.is_file());
+    files.sort();
+    Ok(files
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let record = fs::read(path)
+                .map(|data| Record {
+                    data,
+                    ..Default::default()
+                })
+                .map_err(|e| format!("{}: {}", path.display(), e));
+            (i + 1, record)
+        })
+        .collect())
+}
+
+fn read_ndjson(text: &str) -> Vec<Line> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, parse_ndjson_line(line)))
+        .collect()
+}
+
+fn parse_ndjson_line(line: &str) -> Result<Record, String> {
+    let Value::Object(mut object) = serde_json::from_str(line).map_err(|e| e.to_string())? else {
+        return Err("Expected a JSON object".to_string());
+    };
+    let data = match (object.remove("data"), object.remove("data_base64")) {
+        (Some(_), Some(_)) => return Err("Set either data or data_base64, not both".to_string()),
+        (Some(Value::String(text)), None) => text.into_bytes(),
+        (Some(value), None) => value.to_string().into_bytes(),
+        (None, Some(Value::String(encoded))) => STANDARD
+            .decode(encoded)
+            .map_err(|e| format!("data_base64: {e}"))?,
+        (None, Some(_)) => return Err("data_base64 must be a string".to_string()),
+        (None, None) => Vec::new(),
+    };
+    let attributes = match object.remove("attributes") {
+        None | Some(Value::Null) => HashMap::new(),
+        Some(Value::Object(map)) => map
+            .into_iter()
+            .map(|(k, v)| match v {
+                Value::String(s) => Ok((k, s)),
+                _ => Err(format!("Attribute '{k}' must be a string")),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(_) => return Err("attributes must be an object".to_string()),
+    };
+    let ordering_key = match object.remove("ordering_key") {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(key)) => key,
+        Some(_) => return Err("ordering_key must be a string".to_string()),
+    };
+    Ok(Record {
+        data,
+        attributes,
+        ordering_key,
+    })
+}
+
+fn read_csv(text: &str) -> Result<Vec<Line>, String> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let payload = headers.iter().position(|h| h == "payload");
+    let payload_base64 = headers.iter().position(|h| h == "payload_base64");
+    if payload.is_none() && payload_base64.is_none() {
+        return Err("The CSV header needs a payload or payload_base64 column".to_string());
+    }
+    let ordering_key = headers.iter().position(|h| h == "ordering_key");
+    let metadata: Vec<Option<usize>> = METADATA_COLUMNS
+        .iter()
+        .map(|column| headers.iter().position(|h| h == *column))
+        .collect();
+    let lines = reader
+        .records()
+        .map(|row| {
+            // Rows are numbered by the line they start on, the header being 1.
+            let line = match &row {
+                Ok(row) => row.position(),
+                Err(e) => e.position(),
+            }
+            .map_or(0, |p| p.line() as usize);
+            let record = row.map_err(|e| e.to_string()).and_then(|row| {
+                let encoded = payload_base64
+                    .and_then(|i| row.get(i))
+                    .filter(|e| !e.is_empty());
+                let data = match encoded {
+                    Some(encoded) => STANDARD
+                        .decode(encoded)
+                        .map_err(|e| format!("payload_base64: {e}"))?,
+                    None => payload
+                        .and_then(|i| row.get(i))
+                        .unwrap_or_default()
+                        .as_bytes()
+                        .to_vec(),
+                };
+                let attributes = headers
+                    .iter()
+                    .zip(row.iter())
+                    .enumerate()
+                    .filter(|(i, (_, value))| {
+                        ![payload, payload_base64, ordering_key].contains(&Some(*i))
+                            && !metadata.contains(&Some(*i))
+                            && !value.is_empty()
+                    })
+                    .map(|(_, (k, v))| (k.to_string(), v.to_string()))
+                    .collect();
+                Ok(Record {
+                    data,
+                    attributes,
+                    ordering_key: ordering_key
+                        .and_then(|i| row.get(i))
+                        .unwrap_or_default()
+                        .to_string(),
+                })
+            });
+            (line, record)
+        })
+        .collect();
+    Ok(lines)
+}
+
+/// Writes `messages` to `path` in `format`, creating the directory for raw
+/// payloads. Nothing that already exists is overwritten.
+pub fn write(path: &Path, format: Format, messages: &[&MessageInfo]) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    let result = match format {
+        Format::Ndjson => fs::write(path, to_ndjson(messages)).map_err(|e| e.to_string()),
+        Format::Csv => {
+            to_csv(messages).and_then(|csv| fs::write(path, csv).map_err(|e| e.to_string()))
+        }
+        Format::Raw => write_raw(path, messages).map_err(|e| e.to_string()),
+    };
+    result.map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// One line per message. Payloads that aren't UTF-8 are written as
+/// `data_base64`.
+fn to_ndjson(messages: &[&MessageInfo]) -> String {
+    let mut out = String::new();
+    for message in messages {
+        let mut object = Map::new();
+        match std::str::from_utf8(&message.data) {
+            Ok(text) => object.insert("data".into(), text.into()),
+            Err(_) => object.insert("data_base64".into(), STANDARD.encode(&message.data).into()),
+        };
+        let attributes: Map<String, Value> = message
+            .attributes
+            .iter()
+            .map(|(k, v)| (k.clone(), v.as_str().into()))
+            .collect();
+        object.insert("attributes".into(), attributes.into());
+        object.insert("ordering_key".into(), message.ordering_key.as_str().into());
+        object.insert("message_id".into(), message.message_id.as_str().into());
+        if let Some(time) = message.publish_time {
+            object.insert("publish_time".into(), format_timestamp(time).into());
+        }
+        out.push_str(&Value::Object(object).to_string());
+        out.push('\n');
+    }
+    out
+}
+
+/// A column per attribute key seen in any of the messages. Attributes named
+/// like one of the message's own columns would be read back as that column,
+/// so they can only be exported to NDJSON.
+fn to_csv(messages: &[&MessageInfo]) -> Result<Vec<u8>, String> {
+    let keys: BTreeSet<&String> = messages.iter().flat_map(|m| m.attributes.keys()).collect();
+    let columns = METADATA_COLUMNS
+        .into_iter()
+        .chain(["ordering_key", "payload", "payload_base64"]);
+    if let Some(key) = columns.clone().find(|c| keys.contains(&c.to_string())) {
+        return Err(format!(
+            "The attribute '{key}' has the name of a CSV column, export to NDJSON instead"
+        ));
+    }
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let header = columns.chain(keys.iter().map(|k| k.as_str()));
+    writer.write_record(header).map_err(|e| e.to_string())?;
+    for message in messages {
+        let (payload, payload_base64) = match std::str::from_utf8(&message.data) {
+            Ok(text) => (text.to_string(), String::new()),
+            Err(_) => (String::new(), STANDARD.encode(&message.data)),
+        };
+        let row = [
+            message.message_id.clone(),
+            message
+                .publish_time
+                .map(format_timestamp)
+                .unwrap_or_default(),
+            message.ordering_key.clone(),
+            payload,
+            payload_base64,
+        ]
+        .into_iter()
+        .chain(
+            keys.iter()
+                .map(|k| message.attributes.get(*k).cloned().unwrap_or_default()),
+        );
+        writer.write_record(row).map_err(|e| e.to_string())?;
+    }
+    writer.into_inner().map_err(|e| e.to_string())
+}
+
+/// Files are numbered so they publish again in the same order, and named
+/// after the message ID with an extension that suits the payload.
+fn write_raw(dir: &Path, messages: &[&MessageInfo]) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, message) in messages.iter().enumerate() {
+        let extension = match std::str::from_utf8(&message.data) {
+            Ok(text) if serde_json::from_str::<Value>(text).is_ok() => "json",
+            Ok(_) => "txt",
+            Err(_) => "bin",
+        };
+        let name = format!("{:06}-{}.{}", i + 1, message.message_id, extension);
+        fs::write(dir.join(name), &message.data)?;
+    }
+    Ok(())
+}
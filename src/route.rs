@@ -1,5 +1,5 @@
 use crate::app::App;
-use crate::component::debug::debug_log;
+use crate::component::{debug::debug_log, schemas};
 use crate::event::AppEvent;
 use ratatui::{style::Stylize, text::Line};
 use strum::IntoEnumIterator;
@@ -12,6 +12,14 @@ pub enum Route {
     Config,
     #[strum(serialize = "Topics")]
     Topics,
+    #[strum(serialize = "Subscriptions")]
+    Subscriptions,
+    #[strum(serialize = "Messages")]
+    Messages,
+    #[strum(serialize = "Snapshots")]
+    Snapshots,
+    #[strum(serialize = "Schemas")]
+    Schemas,
 }
 
 impl Route {
@@ -62,5 +70,10 @@ pub fn on_event(state: &mut App, event: RouteEvent) -> Option<AppEvent> {
         RouteEvent::Prev => state.route.previous(),
     };
     state.route = new_route;
-    None
+    match new_route {
+        Route::Schemas if state.pubsub.schemas.revisions.is_empty() => {
+            schemas::load_revisions(&state.pubsub.schemas)
+        }
+        _ => None,
+    }
 }
@@ -0,0 +1,290 @@
+use base64::Engine;
+use std::{
+    collections::HashMap,
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
+/// Returns the last path segment of a fully qualified resource name,
+/// e.g. `projects/p/subscriptions/orders` -> `orders`.
+pub fn short_name(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+/// Formats a duration using the largest whole units, e.g. `7d`, `1h30m`, `45s`.
+pub fn format_duration(duration: Duration) -> String {
+    let mut secs = duration.as_secs();
+    if secs == 0 {
+        return "0s".to_string();
+    }
+    let mut out = String::new();
+    for (unit, size) in [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)] {
+        if secs >= size {
+            out.push_str(&format!("{}{}", secs / size, unit));
+            secs %= size;
+        }
+    }
+    out
+}
+
+/// Formats a point in time as RFC3339 in UTC, with millisecond precision.
+pub fn format_timestamp(time: SystemTime) -> String {
+    let mut timestamp = prost_types::Timestamp::from(time);
+    timestamp.nanos -= timestamp.nanos % 1_000_000;
+    timestamp.to_string()
+}
+
+/// Parses a duration such as `7d`, `1h30m`, `90s` or a bare number of seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let size = match c {
+            'd' => 86_400,
+            'h' => 3_600,
+            'm' => 60,
+            's' => 1,
+            _ => return Err(format!("Invalid duration unit '{c}' in '{input}'")),
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("Invalid duration '{input}'"))?;
+        total = value
+            .checked_mul(size)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("Duration '{input}' is too long"))?;
+        digits.clear();
+    }
+    if !digits.is_empty() || input.is_empty() {
+        return Err(format!(
+            "Invalid duration '{input}', expected e.g. 7d or 1h30m"
+        ));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// The highest rate limit accepted, in messages per second.
+pub const MAX_RATE: f64 = 1_000_000.0;
+
+/// Parses a rate limit in messages per second into the time between
+/// messages. A rate of 0 means no limit and gives `None`.
+pub fn parse_rate(input: &str) -> Result<Option<Duration>, String> {
+    let rate: f64 = input
+        .trim()
+        .parse()
+        .ok()
+        .filter(|r: &f64| r.is_finite() && *r >= 0.0)
+        .ok_or("The rate must be a number of messages per second")?;
+    if rate == 0.0 {
+        return Ok(None);
+    }
+    if rate > MAX_RATE {
+        return Err(format!(
+            "The rate can be at most {MAX_RATE} messages per second"
+        ));
+    }
+    Duration::try_from_secs_f64(1.0 / rate)
+        .ok()
+        .filter(|period| !period.is_zero())
+        .map(Some)
+        .ok_or_else(|| format!("The rate {rate} is too low"))
+}
+
+/// Parses an RFC3339 timestamp, or a time relative to `now` such as `-15m`.
+pub fn parse_time(input: &str, now: SystemTime) -> Result<SystemTime, String> {
+    let input = input.trim();
+    if let Some(ago) = input.strip_prefix('-') {
+        let ago = parse_duration(ago)?;
+        return now
+            .checked_sub(ago)
+            .ok_or_else(|| format!("'{input}' is too far in the past"));
+    }
+    input
+        .parse::<prost_types::Timestamp>()
+        .ok()
+        .and_then(|t| SystemTime::try_from(t).ok())
+        .ok_or_else(|| format!("Invalid time '{input}', expected RFC3339 or e.g. -15m"))
+}
+
+/// Parses `key=value` pairs separated by commas. Empty input yields an empty map.
+pub fn parse_key_values(input: &str) -> Result<HashMap<String, String>, String> {
+    let mut pairs = HashMap::new();
+    for pair in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("'{pair}' is not in key=value form"))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("'{pair}' has an empty key"));
+        }
+        if pairs
+            .insert(key.to_string(), value.trim().to_string())
+            .is_some()
+        {
+            return Err(format!("Duplicate key '{key}'"));
+        }
+    }
+    Ok(pairs)
+}
+
+/// Checks a topic, subscription or snapshot ID against Pub/Sub naming rules:
+/// 3-255 characters, starting with a letter, using only letters, digits and
+/// `-_.~+%`, and not starting with `goog`.
+pub fn validate_resource_id(id: &str) -> Result<(), String> {
+    if !(3..=255).contains(&id.len()) {
+        return Err(format!("'{id}' must be between 3 and 255 characters"));
+    }
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(format!("'{id}' must start with a letter"));
+    }
+    if id.to_ascii_lowercase().starts_with("goog") {
+        return Err(format!("'{id}' must not start with 'goog'"));
+    }
+    if let Some(c) = id
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !"-_.~+%".contains(*c))
+    {
+        return Err(format!("'{id}' contains invalid character '{c}'"));
+    }
+    Ok(())
+}
+
+/// Checks labels against Cloud resource label rules: keys start with a
+/// lowercase letter, and keys and values use at most 63 lowercase letters,
+/// digits, `_` or `-`.
+pub fn validate_labels(labels: &HashMap<String, String>) -> Result<(), String> {
+    fn valid(s: &str) -> bool {
+        s.len() <= 63
+            && s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    }
+    for (key, value) in labels {
+        if !key.starts_with(|c: char| c.is_ascii_lowercase()) || !valid(key) {
+            return Err(format!("Invalid label key '{key}'"));
+        }
+        if !valid(value) {
+            return Err(format!("Invalid value for label '{key}'"));
+        }
+    }
+    Ok(())
+}
+
+/// Matches a topic name against a pattern where `*` stands for any run of
+/// characters. Patterns without a `/` are matched against the short name.
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let name = match pattern.contains('/') {
+        true => name,
+        false => short_name(name),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Copies `text` to the clipboard using the OSC 52 terminal escape, which
+/// most terminals support, including over SSH.
+pub fn copy_to_clipboard(text: &str) -> std::io::Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{encoded}\x07")?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration_accepts_units_and_bare_seconds() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5_400)));
+        assert_eq!(parse_duration(" 7d "), Ok(Duration::from_secs(604_800)));
+        assert_eq!(parse_duration("1d2h3m4s"), Ok(Duration::from_secs(93_784)));
+    }
+
+    #[test]
+    fn parse_duration_rejects_bad_input() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("1h30").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_overflow() {
+        assert!(parse_duration("99999999999999999d").is_err());
+        assert!(parse_duration("18446744073709551615s1s").is_err());
+    }
+
+    #[test]
+    fn parse_rate_gives_the_period_between_messages() {
+        assert_eq!(parse_rate("0"), Ok(None));
+        assert_eq!(parse_rate("4"), Ok(Some(Duration::from_millis(250))));
+        assert_eq!(parse_rate("1000000"), Ok(Some(Duration::from_micros(1))));
+        assert!(parse_rate("1e10").is_err());
+        assert!(parse_rate("1e-300").is_err());
+        assert!(parse_rate("-1").is_err());
+        assert!(parse_rate("NaN").is_err());
+        assert!(parse_rate("fast").is_err());
+    }
+
+    #[test]
+    fn parse_time_accepts_rfc3339_and_relative_times() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        assert_eq!(parse_time("-15m", now), Ok(now - Duration::from_secs(900)));
+        assert_eq!(
+            parse_time("1970-01-02T00:00:00Z", now),
+            Ok(SystemTime::UNIX_EPOCH + Duration::from_secs(86_400))
+        );
+        assert!(parse_time("-18446744073709551615", now).is_err());
+        assert!(parse_time("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn parse_key_values_splits_and_trims_pairs() {
+        let pairs = parse_key_values(" env = prod, team=core ,").unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs["env"], "prod");
+        assert_eq!(pairs["team"], "core");
+        assert_eq!(parse_key_values("url=a=b").unwrap()["url"], "a=b");
+        assert!(parse_key_values("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_key_values_rejects_bad_pairs() {
+        assert!(parse_key_values("env").is_err());
+        assert!(parse_key_values("=prod").is_err());
+        assert!(parse_key_values("env=a,env=b").is_err());
+    }
+
+    #[test]
+    fn validate_resource_id_follows_naming_rules() {
+        assert!(validate_resource_id("orders-v1.2~x+y%z_").is_ok());
+        assert!(validate_resource_id("ab").is_err());
+        assert!(validate_resource_id(&"a".repeat(256)).is_err());
+        assert!(validate_resource_id("1orders").is_err());
+        assert!(validate_resource_id("Google-topic").is_err());
+        assert!(validate_resource_id("orders/v1").is_err());
+    }
+}
@@ -8,9 +8,9 @@ use ratatui::{
 use crate::{
     app::App,
     component::{
-        debug, header,
+        debug, header, messages,
         pubsub::{self, draw_pubsub_status},
-        topics,
+        schemas, snapshots, subscriptions, topics,
     },
     route::Route,
 };
@@ -41,6 +41,18 @@ fn draw_main(state: &App, f: &mut Frame, area: Rect) {
         Route::Topics => {
             topics::draw(state, f, area);
         }
+        Route::Subscriptions => {
+            subscriptions::draw(state, f, area);
+        }
+        Route::Messages => {
+            messages::draw(state, f, area);
+        }
+        Route::Snapshots => {
+            snapshots::draw(state, f, area);
+        }
+        Route::Schemas => {
+            schemas::draw(state, f, area);
+        }
     }
 }
 